@@ -0,0 +1,259 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::error::Error;
+use std::fmt;
+use std::fs::read_to_string;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+use log::debug;
+
+use crate::word_tree::WordTree;
+
+const BUILT_IN: &[(Category, &str)] = &[
+    (Category::Vulgar, include_str!("blocklist/vulgar.txt")),
+    (Category::Slur, include_str!("blocklist/slur.txt")),
+    (Category::Medical, include_str!("blocklist/medical.txt")),
+];
+
+/// Why a word is kept away from players.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Category {
+    Vulgar,
+    Slur,
+    /// Anatomical and medical terms that are fine in a dictionary but not on a family puzzle.
+    Medical,
+}
+
+impl Category {
+    pub const ALL: [Category; 3] = [Category::Vulgar, Category::Slur, Category::Medical];
+}
+
+/// Error for a string that is not the name of a [`Category`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseCategoryError(String);
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Category::Vulgar => write!(f, "vulgar"),
+            Category::Slur => write!(f, "slur"),
+            Category::Medical => write!(f, "medical"),
+        }
+    }
+}
+
+impl FromStr for Category {
+    type Err = ParseCategoryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Category::ALL
+            .into_iter()
+            .find(|c| c.to_string().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| ParseCategoryError(s.to_string()))
+    }
+}
+
+impl fmt::Display for ParseCategoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "'{}' is not a category, expected vulgar, slur or medical",
+            self.0
+        )
+    }
+}
+
+impl Error for ParseCategoryError {}
+
+/// Words to filter out of a word list or a list of solutions, each filed under a [`Category`].
+///
+/// Only words in the enabled categories are blocked, which is all of them unless
+/// [`Blocklist::with_categories`] says otherwise.
+#[derive(Debug, Clone)]
+pub struct Blocklist {
+    words: HashMap<String, Category>,
+    enabled: BTreeSet<Category>,
+}
+
+/// How many words a [`Blocklist`] took out, by category.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BlockStats {
+    counts: BTreeMap<Category, usize>,
+}
+
+impl Default for Blocklist {
+    fn default() -> Self {
+        Blocklist {
+            words: HashMap::new(),
+            enabled: Category::ALL.into_iter().collect(),
+        }
+    }
+}
+
+impl Blocklist {
+    /// An empty blocklist.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The list shipped with the crate.
+    pub fn built_in() -> Self {
+        BUILT_IN
+            .iter()
+            .fold(Self::new(), |list, (category, words)| {
+                list.with_words(*category, words.lines())
+            })
+    }
+
+    /// Adds words from a file, one per line, e.g. to extend the built-in list for a particular
+    /// audience.
+    pub fn with_file<P: AsRef<Path>>(self, category: Category, path: P) -> io::Result<Self> {
+        Ok(self.with_words(category, read_to_string(path)?.lines()))
+    }
+
+    pub fn with_words<I, S>(mut self, category: Category, words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        for word in words {
+            let word = word.as_ref().trim().to_lowercase();
+            if !word.is_empty() {
+                self.words.insert(word, category);
+            }
+        }
+        self
+    }
+
+    /// Only blocks words in `categories`.
+    pub fn with_categories(mut self, categories: &[Category]) -> Self {
+        self.enabled = categories.iter().copied().collect();
+        self
+    }
+
+    /// The category `word` is blocked under, `None` if it is allowed.
+    pub fn category(&self, word: &str) -> Option<Category> {
+        self.words
+            .get(word)
+            .copied()
+            .filter(|c| self.enabled.contains(c))
+    }
+
+    pub fn is_blocked(&self, word: &str) -> bool {
+        self.category(word).is_some()
+    }
+
+    /// Removes blocked words, e.g. from a puzzle's solutions, counting what was taken out.
+    pub fn filter(&self, words: &mut Vec<String>) -> BlockStats {
+        let mut stats = BlockStats::default();
+        words.retain(|word| match self.category(word) {
+            Some(category) => {
+                stats.add(category);
+                false
+            }
+            None => true,
+        });
+        debug!("Blocked {}", stats);
+        stats
+    }
+
+    /// Removes blocked words from a tree that has already been built, e.g. one loaded from the
+    /// cache, counting what was taken out.
+    pub fn remove_from(&self, tree: &mut WordTree) -> BlockStats {
+        let mut stats = BlockStats::default();
+        for (word, category) in &self.words {
+            if self.enabled.contains(category) && tree.remove(word) {
+                stats.add(*category);
+            }
+        }
+        debug!("Blocked {}", stats);
+        stats
+    }
+}
+
+impl BlockStats {
+    pub(crate) fn add(&mut self, category: Category) {
+        *self.counts.entry(category).or_default() += 1;
+    }
+
+    pub fn get(&self, category: Category) -> usize {
+        self.counts.get(&category).copied().unwrap_or_default()
+    }
+
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+}
+
+impl fmt::Display for BlockStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let counts = Category::ALL
+            .iter()
+            .map(|c| format!("{} {}", self.get(*c), c))
+            .collect::<Vec<String>>();
+        write!(f, "{}", counts.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::init_logger;
+
+    use super::*;
+
+    #[test]
+    fn built_in() {
+        init_logger();
+        let list = Blocklist::built_in();
+        assert_eq!(list.category("fuck"), Some(Category::Vulgar));
+        assert_eq!(list.category("rectum"), Some(Category::Medical));
+        assert_eq!(list.category("kind"), None);
+
+        let list = list.with_categories(&[Category::Vulgar, Category::Slur]);
+        assert_eq!(list.category("rectum"), None);
+        assert!(list.is_blocked("fuck"));
+    }
+
+    #[test]
+    fn filter() {
+        init_logger();
+        let list = Blocklist::new()
+            .with_words(Category::Vulgar, ["poop", "Bum "])
+            .with_words(Category::Medical, ["navel"]);
+        let mut words = ["bum", "kind", "navel", "poop", "prison"]
+            .map(String::from)
+            .to_vec();
+
+        let stats = list.filter(&mut words);
+        assert_eq!(words, ["kind", "prison"]);
+        assert_eq!(stats.get(Category::Vulgar), 2);
+        assert_eq!(stats.total(), 3);
+        assert_eq!(stats.to_string(), "2 vulgar, 0 slur, 1 medical");
+    }
+
+    #[test]
+    fn remove_from_tree() {
+        init_logger();
+        let list = Blocklist::new()
+            .with_words(Category::Vulgar, ["poop"])
+            .with_words(Category::Medical, ["navel"])
+            .with_categories(&[Category::Vulgar]);
+        let mut tree = WordTree::new(&["kind", "navel", "poop", "poops"].map(String::from));
+
+        let stats = list.remove_from(&mut tree);
+        assert_eq!(stats.total(), 1);
+        assert!(!tree.contains("poop"));
+        assert!(tree.contains("poops"));
+        assert!(tree.contains("navel"));
+    }
+
+    #[test]
+    fn parse_category() {
+        assert_eq!("Slur".parse(), Ok(Category::Slur));
+        assert_eq!(
+            "rude".parse::<Category>(),
+            Err(ParseCategoryError("rude".to_string()))
+        );
+    }
+}
@@ -0,0 +1,304 @@
+use std::collections::BTreeMap;
+use std::io;
+use std::path::Path;
+
+use log::{debug, info};
+
+use crate::{
+    loader::{report::LoadReport, source::Source, Loader},
+    word_tree::WordTree,
+};
+use blocklist::{BlockStats, Blocklist};
+
+pub mod blocklist;
+
+/// What a layer does to the words loaded before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayerKind {
+    /// The main word list.
+    Base,
+    /// Extra words missing from the base list.
+    Additions,
+    /// Words to take out of everything loaded so far.
+    Removals,
+}
+
+/// One word list in a [`Dictionary`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Layer {
+    pub kind: LayerKind,
+    pub source: Source,
+}
+
+/// An ordered stack of word lists, e.g. a downloaded list plus local fixes kept in their own
+/// files. Layers are applied in the order they were added, so a word removed by one layer can be
+/// added back by a later one.
+#[derive(Debug, Clone, Default)]
+pub struct Dictionary {
+    layers: Vec<Layer>,
+    blocklist: Option<Blocklist>,
+}
+
+/// The words left once every layer of a [`Dictionary`] has been applied.
+pub struct Merged {
+    tree: WordTree,
+    layers: Vec<Layer>,
+    /// Index into `layers` of the layer each word was added by.
+    origins: BTreeMap<String, usize>,
+    /// What happened to the lines of each layer, in the same order as `layers`.
+    reports: Vec<LoadReport>,
+    blocked: BlockStats,
+}
+
+impl Dictionary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_base<P: AsRef<Path>>(self, path: P) -> Self {
+        self.with_layer(LayerKind::Base, Source::file(path))
+    }
+
+    pub fn with_additions<P: AsRef<Path>>(self, path: P) -> Self {
+        self.with_layer(LayerKind::Additions, Source::file(path))
+    }
+
+    pub fn with_removals<P: AsRef<Path>>(self, path: P) -> Self {
+        self.with_layer(LayerKind::Removals, Source::file(path))
+    }
+
+    pub fn with_layer(mut self, kind: LayerKind, source: Source) -> Self {
+        self.layers.push(Layer { kind, source });
+        self
+    }
+
+    /// Leaves out blocked words once every layer has been applied, so an additions file can not
+    /// bring them back.
+    pub fn with_blocklist(mut self, blocklist: Blocklist) -> Self {
+        self.blocklist = Some(blocklist);
+        self
+    }
+
+    pub fn layers(&self) -> &[Layer] {
+        &self.layers
+    }
+
+    /// Loads every word from every layer.
+    pub fn load(&self) -> io::Result<Merged> {
+        self.load_with(&Loader::new())
+    }
+
+    /// Loads the words for nine letter puzzles, like [`crate::load_9p_like_words`].
+    pub fn load_9p_like(&self) -> io::Result<Merged> {
+        self.load_with(&Loader::nine_letter())
+    }
+
+    /// Reads each layer with `loader`. Removals are normalized the same way, but apply whatever
+    /// the loader's filter says.
+    pub fn load_with(&self, loader: &Loader) -> io::Result<Merged> {
+        let removals_loader = Loader::new().with_normalizer(loader.normalizer().clone());
+        let mut origins = BTreeMap::new();
+        let mut reports = Vec::with_capacity(self.layers.len());
+        for (i, layer) in self.layers.iter().enumerate() {
+            let before = origins.len();
+            match layer.kind {
+                LayerKind::Base | LayerKind::Additions => {
+                    let (words, report) = loader.read_from(&layer.source)?;
+                    reports.push(report);
+                    for word in words {
+                        origins.entry(word).or_insert(i);
+                    }
+                }
+                LayerKind::Removals => {
+                    let (words, report) = removals_loader.read_from(&layer.source)?;
+                    reports.push(report);
+                    for word in words {
+                        origins.remove(&word);
+                    }
+                }
+            }
+            debug!(
+                "{:?} layer {} took the word count from {} to {}",
+                layer.kind,
+                layer.source,
+                before,
+                origins.len()
+            );
+        }
+
+        let mut blocked = BlockStats::default();
+        if let Some(blocklist) = &self.blocklist {
+            origins.retain(|word, _| match blocklist.category(word) {
+                Some(category) => {
+                    blocked.add(category);
+                    false
+                }
+                None => true,
+            });
+        }
+        info!(
+            "Merged {} layers into {} words, blocked {}",
+            self.layers.len(),
+            origins.len(),
+            blocked
+        );
+
+        let words = origins.keys().cloned().collect::<Vec<String>>();
+        Ok(Merged {
+            tree: WordTree::new(&words),
+            layers: self.layers.clone(),
+            origins,
+            reports,
+            blocked,
+        })
+    }
+}
+
+impl Merged {
+    pub fn tree(&self) -> &WordTree {
+        &self.tree
+    }
+
+    pub fn into_tree(self) -> WordTree {
+        self.tree
+    }
+
+    /// The layer that added `word`, `None` if it is not in the dictionary.
+    pub fn origin(&self, word: &str) -> Option<&Layer> {
+        self.origins.get(word).map(|i| &self.layers[*i])
+    }
+
+    /// Each layer with the report from loading it.
+    pub fn reports(&self) -> impl Iterator<Item = (&Layer, &LoadReport)> {
+        self.layers.iter().zip(&self.reports)
+    }
+
+    /// Words left out by the dictionary's blocklist.
+    pub fn blocked(&self) -> &BlockStats {
+        &self.blocked
+    }
+
+    /// How many of the words were added by a layer of `kind`.
+    pub fn count_from(&self, kind: LayerKind) -> usize {
+        self.origins
+            .values()
+            .filter(|i| self.layers[**i].kind == kind)
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use crate::init_logger;
+
+    use super::blocklist::Category;
+    use super::*;
+
+    fn sorted_words(merged: &Merged) -> Vec<String> {
+        let mut words = merged.tree().get_words();
+        words.sort();
+        words
+    }
+
+    fn write_list(name: &str, words: &[&str]) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("nine_q_{}_{}.txt", name, std::process::id()));
+        fs::write(&path, words.join("\n")).unwrap();
+        path
+    }
+
+    #[test]
+    fn layers() {
+        init_logger();
+        let base = write_list(
+            "base",
+            &["kind", "kinder", "kindest", "kindheartedness", "badword"],
+        );
+        let additions = write_list("additions", &["kindly", "kind"]);
+        let removals = write_list("removals", &["badword", "kindly"]);
+        let additions_again = write_list("additions_again", &["kindly"]);
+
+        let dictionary = Dictionary::new()
+            .with_base(&base)
+            .with_additions(&additions)
+            .with_removals(&removals)
+            .with_additions(&additions_again);
+        let merged = dictionary.load().unwrap();
+
+        assert_eq!(
+            sorted_words(&merged),
+            ["kind", "kinder", "kindest", "kindheartedness", "kindly"]
+        );
+        assert_eq!(merged.origin("kind").unwrap().source, Source::file(&base));
+        assert_eq!(
+            merged.origin("kindly").unwrap().source,
+            Source::file(&additions_again)
+        );
+        assert_eq!(merged.origin("badword"), None);
+        assert_eq!(merged.count_from(LayerKind::Base), 4);
+        assert_eq!(merged.count_from(LayerKind::Additions), 1);
+        let reports = merged
+            .reports()
+            .map(|(_, r)| r.lines)
+            .collect::<Vec<usize>>();
+        assert_eq!(reports, [5, 2, 2, 1]);
+
+        let merged = dictionary.load_9p_like().unwrap();
+        assert_eq!(
+            sorted_words(&merged),
+            ["kind", "kinder", "kindest", "kindly"]
+        );
+
+        for path in [base, additions, removals, additions_again] {
+            fs::remove_file(path).unwrap();
+        }
+    }
+
+    #[test]
+    fn blocklist() {
+        init_logger();
+        let base = write_list("blocked_base", &["kind", "poop", "navel"]);
+        let additions = write_list("blocked_additions", &["poop"]);
+
+        let blocklist = Blocklist::new()
+            .with_words(Category::Vulgar, ["poop"])
+            .with_words(Category::Medical, ["navel"]);
+        let merged = Dictionary::new()
+            .with_base(&base)
+            .with_additions(&additions)
+            .with_blocklist(blocklist)
+            .load()
+            .unwrap();
+
+        assert_eq!(sorted_words(&merged), ["kind"]);
+        assert_eq!(merged.blocked().get(Category::Vulgar), 1);
+        assert_eq!(merged.blocked().total(), 2);
+
+        for path in [base, additions] {
+            fs::remove_file(path).unwrap();
+        }
+    }
+
+    #[test]
+    fn in_memory_layers() {
+        init_logger();
+        let merged = Dictionary::new()
+            .with_layer(LayerKind::Base, Source::text("kind\nkinder\n"))
+            .with_layer(LayerKind::Removals, Source::text("Kinder\n"))
+            .load_9p_like()
+            .unwrap();
+        assert_eq!(sorted_words(&merged), ["kind"]);
+    }
+
+    #[test]
+    fn missing_layer() {
+        init_logger();
+        let dictionary = Dictionary::new().with_base("this/file/does/not/exist.txt");
+        assert_eq!(
+            dictionary.load().err().map(|e| e.kind()),
+            Some(io::ErrorKind::NotFound)
+        );
+    }
+}
@@ -0,0 +1,329 @@
+use std::fmt;
+use std::sync::Arc;
+
+use log::debug;
+
+use super::{Game, InvalidGuess, NineP};
+use crate::{rng::Rng, word_tree::WordTree};
+
+/// Letters on a Countdown board.
+pub const BOARD_SIZE: usize = 9;
+/// Fewest vowels a board may have.
+pub const MIN_VOWELS: usize = 3;
+/// Fewest consonants a board may have.
+pub const MIN_CONSONANTS: usize = 4;
+
+/// How many of each vowel are in the bag, as on the show.
+const VOWELS: &[(char, usize)] = &[('a', 15), ('e', 21), ('i', 13), ('o', 13), ('u', 5)];
+/// How many of each consonant are in the bag, as on the show.
+const CONSONANTS: &[(char, usize)] = &[
+    ('b', 2),
+    ('c', 3),
+    ('d', 6),
+    ('f', 2),
+    ('g', 3),
+    ('h', 2),
+    ('j', 1),
+    ('k', 1),
+    ('l', 5),
+    ('m', 4),
+    ('n', 8),
+    ('p', 4),
+    ('q', 1),
+    ('r', 9),
+    ('s', 9),
+    ('t', 9),
+    ('v', 1),
+    ('w', 1),
+    ('x', 1),
+    ('y', 1),
+    ('z', 1),
+];
+
+/// A contestant's call for the next letter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pick {
+    Vowel,
+    Consonant,
+}
+
+/// Reasons a list of [`Pick`]s can not make a board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidPicks {
+    /// A board takes exactly [`BOARD_SIZE`] picks, this many were given.
+    Count(usize),
+    /// Fewer than [`MIN_VOWELS`] vowels were picked.
+    TooFewVowels(usize),
+    /// Fewer than [`MIN_CONSONANTS`] consonants were picked.
+    TooFewConsonants(usize),
+}
+
+impl fmt::Display for InvalidPicks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidPicks::Count(n) => write!(f, "expected {} picks, got {}", BOARD_SIZE, n),
+            InvalidPicks::TooFewVowels(n) => {
+                write!(f, "at least {} vowels are needed, got {}", MIN_VOWELS, n)
+            }
+            InvalidPicks::TooFewConsonants(n) => write!(
+                f,
+                "at least {} consonants are needed, got {}",
+                MIN_CONSONANTS, n
+            ),
+        }
+    }
+}
+
+impl std::error::Error for InvalidPicks {}
+
+/// How a declared word measured up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Declaration {
+    pub points: u32,
+    /// Length of the longest word on the board.
+    pub best_length: usize,
+    /// Whether nobody could have done better.
+    pub is_best: bool,
+}
+
+/// The two shuffled piles letters are drawn from.
+#[derive(Debug, Clone)]
+pub struct Bags {
+    vowels: Vec<char>,
+    consonants: Vec<char>,
+}
+
+impl Bags {
+    /// Full bags, shuffled by `seed`.
+    pub fn new(seed: u64) -> Self {
+        let mut rng = Rng::new(seed);
+        let mut vowels = fill(VOWELS);
+        let mut consonants = fill(CONSONANTS);
+        rng.shuffle(&mut vowels);
+        rng.shuffle(&mut consonants);
+        Bags { vowels, consonants }
+    }
+
+    /// Takes the top letter from the pile picked, `None` once it is empty.
+    pub fn draw(&mut self, pick: Pick) -> Option<char> {
+        match pick {
+            Pick::Vowel => self.vowels.pop(),
+            Pick::Consonant => self.consonants.pop(),
+        }
+    }
+}
+
+fn fill(counts: &[(char, usize)]) -> Vec<char> {
+    counts
+        .iter()
+        .flat_map(|&(c, n)| std::iter::repeat_n(c, n))
+        .collect()
+}
+
+/// A letters round: nine letters, and the longest word that can be made from them wins.
+pub struct Round {
+    puzzle: NineP,
+}
+
+impl Round {
+    /// Draws a board following the contestant's calls, in order.
+    pub fn new(
+        picks: &[Pick],
+        seed: u64,
+        word_tree: impl Into<Arc<WordTree>>,
+    ) -> Result<Self, InvalidPicks> {
+        if picks.len() != BOARD_SIZE {
+            return Err(InvalidPicks::Count(picks.len()));
+        }
+        let vowels = picks.iter().filter(|p| **p == Pick::Vowel).count();
+        if vowels < MIN_VOWELS {
+            return Err(InvalidPicks::TooFewVowels(vowels));
+        }
+        if BOARD_SIZE - vowels < MIN_CONSONANTS {
+            return Err(InvalidPicks::TooFewConsonants(BOARD_SIZE - vowels));
+        }
+
+        let mut bags = Bags::new(seed);
+        let letters = picks
+            .iter()
+            .map(|p| bags.draw(*p).expect("bags hold more than a board of each"))
+            .collect::<String>();
+        debug!("Drew {} from seed {}", letters, seed);
+
+        Ok(Round {
+            puzzle: NineP::new(letters, word_tree),
+        })
+    }
+
+    /// Draws a board with the calls chosen at random, keeping to the vowel and consonant minimums.
+    pub fn random(seed: u64, word_tree: impl Into<Arc<WordTree>>) -> Self {
+        let mut rng = Rng::new(seed);
+        let vowels = MIN_VOWELS + rng.below(BOARD_SIZE - MIN_VOWELS - MIN_CONSONANTS + 1);
+        let mut picks = [Pick::Consonant; BOARD_SIZE];
+        picks[..vowels].fill(Pick::Vowel);
+        rng.shuffle(&mut picks);
+
+        Round::new(&picks, rng.next_u64(), word_tree).expect("picks are within the limits")
+    }
+
+    pub fn letters(&self) -> &str {
+        self.puzzle.letters()
+    }
+
+    pub fn puzzle(&self) -> &NineP {
+        &self.puzzle
+    }
+
+    /// Every word as long as the longest on the board, A-Z.
+    pub fn longest_words(&self) -> Vec<String> {
+        let mut words = self.puzzle.solve();
+        let best = words.iter().map(|w| w.len()).max().unwrap_or_default();
+        words.retain(|w| w.len() == best);
+        words.sort();
+        words
+    }
+
+    /// Points the way the show gives them: one per letter, doubled for using all nine.
+    pub fn score_word(&self, word: &str) -> u32 {
+        match word.len() {
+            BOARD_SIZE => 2 * BOARD_SIZE as u32,
+            n => n as u32,
+        }
+    }
+
+    /// Scores a declared word and compares it with the best answer. Case and surrounding
+    /// whitespace are ignored, as they are for guesses in a [`super::session::GameSession`].
+    pub fn declare(&self, word: &str) -> Result<Declaration, InvalidGuess> {
+        let word = word.trim().to_lowercase();
+        self.puzzle.check_guess(&word)?;
+        let best_length = self
+            .longest_words()
+            .first()
+            .map(String::len)
+            .unwrap_or_default();
+
+        Ok(Declaration {
+            points: self.score_word(&word),
+            best_length,
+            is_best: word.len() == best_length,
+        })
+    }
+}
+
+impl Game for Round {
+    fn solve(&self) -> Vec<String> {
+        self.puzzle.solve()
+    }
+
+    fn check_guess(&self, guess: &str) -> Result<(), InvalidGuess> {
+        self.puzzle.check_guess(guess)
+    }
+
+    fn score(&self, word: &str) -> u32 {
+        self.score_word(word)
+    }
+
+    /// The letters in the order they were drawn.
+    fn describe_board(&self) -> String {
+        self.letters()
+            .chars()
+            .map(|c| c.to_ascii_uppercase().to_string())
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::init_logger;
+
+    use super::*;
+
+    #[test]
+    fn bags() {
+        init_logger();
+        let mut bags = Bags::new(7);
+        assert_eq!(bags.vowels.len(), 67);
+        assert_eq!(bags.consonants.len(), 74);
+
+        for _ in 0..67 {
+            assert!("aeiou".contains(bags.draw(Pick::Vowel).unwrap()));
+        }
+        assert_eq!(bags.draw(Pick::Vowel), None);
+        assert!(!"aeiou".contains(bags.draw(Pick::Consonant).unwrap()));
+    }
+
+    #[test]
+    fn picks() {
+        init_logger();
+        use Pick::*;
+        let picks = [
+            Vowel, Consonant, Consonant, Vowel, Consonant, Vowel, Consonant, Consonant, Vowel,
+        ];
+        let round = Round::new(&picks, 3, WordTree::default()).unwrap();
+        for (letter, pick) in round.letters().chars().zip(picks) {
+            assert_eq!("aeiou".contains(letter), pick == Vowel);
+        }
+        assert_eq!(
+            Round::new(&picks, 3, WordTree::default())
+                .unwrap()
+                .letters(),
+            round.letters()
+        );
+
+        assert_eq!(
+            Round::new(&picks[1..], 3, WordTree::default()).err(),
+            Some(InvalidPicks::Count(8))
+        );
+        assert_eq!(
+            Round::new(&[Consonant; 9], 3, WordTree::default()).err(),
+            Some(InvalidPicks::TooFewVowels(0))
+        );
+        assert_eq!(
+            Round::new(&[Vowel; 9], 3, WordTree::default()).err(),
+            Some(InvalidPicks::TooFewConsonants(0))
+        );
+    }
+
+    #[test]
+    fn random() {
+        init_logger();
+        for seed in 0..50 {
+            let round = Round::random(seed, WordTree::default());
+            let vowels = round.letters().chars().filter(|c| "aeiou".contains(*c));
+            assert!((MIN_VOWELS..=BOARD_SIZE - MIN_CONSONANTS).contains(&vowels.count()));
+        }
+    }
+
+    #[test]
+    fn declare() {
+        init_logger();
+        let words = ["aspersion", "prison", "person", "spire", "prions"].map(String::from);
+        let round = Round {
+            puzzle: NineP::new("aspersion".to_string(), WordTree::new(&words)),
+        };
+        assert_eq!(round.longest_words(), ["aspersion"]);
+        assert_eq!(
+            round.declare("aspersion"),
+            Ok(Declaration {
+                points: 18,
+                best_length: 9,
+                is_best: true,
+            })
+        );
+        assert_eq!(
+            round.declare("prison"),
+            Ok(Declaration {
+                points: 6,
+                best_length: 9,
+                is_best: false,
+            })
+        );
+        assert_eq!(
+            round.declare("prisoner"),
+            Err(InvalidGuess::UnavailableLetters)
+        );
+        assert_eq!(round.declare("senior"), Err(InvalidGuess::NotAWord));
+        assert_eq!(round.declare(" Aspersion\n"), round.declare("aspersion"));
+    }
+}
@@ -0,0 +1,285 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use log::debug;
+
+use super::generator::Generator;
+use super::NineP;
+use crate::rng::{self, Rng};
+
+/// How many days in a row must not share a board with only one letter changed.
+const NEAR_DUPLICATE_WINDOW: usize = 7;
+
+/// A calendar date (proleptic Gregorian, no time zone).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    /// Days since 1970-01-01.
+    days: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDateError(String);
+
+impl Date {
+    /// `None` if `month` or `day` are out of range.
+    pub fn new(year: i64, month: u32, day: u32) -> Option<Self> {
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return None;
+        }
+        Some(Date {
+            days: days_from_civil(year, month, day),
+        })
+    }
+
+    /// Today's date in UTC.
+    pub fn today() -> Self {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        Date {
+            days: (seconds / 86_400) as i64,
+        }
+    }
+
+    pub fn days_since_epoch(&self) -> i64 {
+        self.days
+    }
+
+    pub fn year(&self) -> i64 {
+        civil_from_days(self.days).0
+    }
+
+    pub fn month(&self) -> u32 {
+        civil_from_days(self.days).1
+    }
+
+    pub fn day(&self) -> u32 {
+        civil_from_days(self.days).2
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (year, month, day) = civil_from_days(self.days);
+        write!(f, "{:04}-{:02}-{:02}", year, month, day)
+    }
+}
+
+impl FromStr for Date {
+    type Err = ParseDateError;
+
+    /// Parses a `YYYY-MM-DD` date.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseDateError(s.to_string());
+        let mut parts = s.trim().splitn(3, '-');
+        let mut next = || parts.next().ok_or_else(error);
+        let year = next()?.parse::<i64>().map_err(|_| error())?;
+        let month = next()?.parse::<u32>().map_err(|_| error())?;
+        let day = next()?.parse::<u32>().map_err(|_| error())?;
+        Date::new(year, month, day).ok_or_else(error)
+    }
+}
+
+impl fmt::Display for ParseDateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "'{}' is not a valid YYYY-MM-DD date", self.0)
+    }
+}
+
+impl Error for ParseDateError {}
+
+impl Generator {
+    /// The board for `date`.
+    ///
+    /// Only depends on the date and the word list, so everyone with the same word list gets the
+    /// same board on the same day. Each set of nine letters is used once before any repeats, and
+    /// (where the word list allows it) boards within a week of each other differ by at least two
+    /// letters.
+    pub fn daily(&self, date: Date) -> Option<NineP> {
+        let schedule = self.daily_schedule();
+        if schedule.is_empty() {
+            return None;
+        }
+
+        let day = date.days_since_epoch();
+        let letters = &schedule[day.rem_euclid(schedule.len() as i64) as usize];
+        debug!("Board for {} uses the letters '{}'", date, letters);
+
+        let mut rng =
+            Rng::new(rng::hash(date.to_string().as_bytes()) ^ rng::hash(letters.as_bytes()));
+        Some(self.board_from_word(letters, &mut rng))
+    }
+
+    /// Every distinct set of nine letters, in the order the days use them.
+    fn daily_schedule(&self) -> Vec<String> {
+        let mut letter_sets = self
+            .nine_letter_words()
+            .iter()
+            .map(|w| {
+                let mut letters: Vec<char> = w.chars().collect();
+                letters.sort_unstable();
+                letters.into_iter().collect::<String>()
+            })
+            .collect::<Vec<String>>();
+        letter_sets.sort();
+        letter_sets.dedup();
+
+        let fingerprint = rng::hash(letter_sets.concat().as_bytes());
+        Rng::new(fingerprint).shuffle(&mut letter_sets);
+
+        // Move near-identical boards away from each other
+        for i in 1..letter_sets.len() {
+            let window = i.saturating_sub(NEAR_DUPLICATE_WINDOW)..i;
+            let fits = |candidate: &String| {
+                letter_sets[window.clone()]
+                    .iter()
+                    .all(|previous| letters_in_common(previous, candidate) < previous.len() - 1)
+            };
+            if let Some(j) = (i..letter_sets.len()).find(|j| fits(&letter_sets[*j])) {
+                letter_sets.swap(i, j);
+            }
+        }
+
+        letter_sets
+    }
+}
+
+/// Letters shared by two sorted strings, counting repeated letters.
+fn letters_in_common(a: &str, b: &str) -> usize {
+    let (mut a, mut b) = (a.chars().peekable(), b.chars().peekable());
+    let mut common = 0;
+    while let (Some(x), Some(y)) = (a.peek(), b.peek()) {
+        match x.cmp(y) {
+            std::cmp::Ordering::Less => {
+                a.next();
+            }
+            std::cmp::Ordering::Greater => {
+                b.next();
+            }
+            std::cmp::Ordering::Equal => {
+                common += 1;
+                a.next();
+                b.next();
+            }
+        }
+    }
+    common
+}
+
+fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since 1970-01-01, see <https://howardhinnant.github.io/date_algorithms.html>.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_from_march = (month as i64 + 9) % 12;
+    let day_of_year = (153 * month_from_march + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_from_march + 2) / 5 + 1) as u32;
+    let month = if month_from_march < 10 {
+        month_from_march + 3
+    } else {
+        month_from_march - 9
+    } as u32;
+    let year = year_of_era + era * 400;
+    (if month <= 2 { year + 1 } else { year }, month, day)
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use crate::{init_logger, word_tree::WordTree};
+
+    use super::*;
+
+    #[test]
+    fn dates() {
+        assert_eq!(Date::new(1970, 1, 1).unwrap().days_since_epoch(), 0);
+        assert_eq!(Date::new(2000, 3, 1).unwrap().days_since_epoch(), 11_017);
+        assert_eq!(
+            "2024-02-29".parse::<Date>().unwrap().to_string(),
+            "2024-02-29"
+        );
+        assert_eq!("1969-12-31".parse::<Date>().unwrap().days_since_epoch(), -1);
+        assert!("2023-02-29".parse::<Date>().is_err());
+        assert!("2023-13-01".parse::<Date>().is_err());
+        assert!("2023-01".parse::<Date>().is_err());
+        assert!("yesterday".parse::<Date>().is_err());
+
+        let date = Date::new(2026, 10, 18).unwrap();
+        assert_eq!((date.year(), date.month(), date.day()), (2026, 10, 18));
+    }
+
+    #[test]
+    fn daily_boards() {
+        init_logger();
+        let words = [
+            "aspersion",
+            "education",
+            "orientals",
+            "relations",
+            "reporting",
+            "reporters",
+            "chocolate",
+            "breakfast",
+            "lightning",
+            "waterfall",
+            "sandstone",
+            "landscape",
+        ]
+        .map(String::from);
+        let generator = Generator::new(WordTree::new(&words));
+        let start = Date::new(2026, 1, 1).unwrap();
+
+        let boards = (0..10)
+            .map(|i| {
+                let date = Date {
+                    days: start.days_since_epoch() + i,
+                };
+                let board = generator.daily(date).unwrap();
+                let again = generator.daily(date).unwrap();
+                assert_eq!(board.letters(), again.letters());
+                assert_eq!(board.centre(), again.centre());
+
+                let mut letters: Vec<char> = board.letters().chars().collect();
+                letters.sort_unstable();
+                letters.into_iter().collect::<String>()
+            })
+            .collect::<Vec<String>>();
+
+        // "orientals" and "relations" are the same letters, leaving ten distinct boards
+        assert_eq!(boards.iter().collect::<HashSet<&String>>().len(), 10);
+        for (i, board) in boards.iter().enumerate() {
+            for previous in &boards[i.saturating_sub(NEAR_DUPLICATE_WINDOW)..i] {
+                assert!(letters_in_common(previous, board) < 8);
+            }
+        }
+    }
+}
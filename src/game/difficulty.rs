@@ -0,0 +1,205 @@
+use std::fmt;
+
+use super::NineP;
+
+/// Relative frequency (percent) of each letter `a..=z` in English text.
+const LETTER_FREQUENCY: [f32; 26] = [
+    8.2, 1.5, 2.8, 4.3, 12.7, 2.2, 2.0, 6.1, 7.0, 0.15, 0.77, 4.0, 2.4, 6.7, 7.5, 1.9, 0.095, 6.0,
+    6.3, 9.1, 2.8, 0.98, 2.4, 0.15, 2.0, 0.074,
+];
+
+/// The most common letter pairs in English words.
+const COMMON_BIGRAMS: &[&str] = &[
+    "th", "he", "in", "er", "an", "re", "on", "at", "en", "nd", "ti", "es", "or", "te", "of", "ed",
+    "is", "it", "al", "ar", "st", "to", "nt", "ng", "se", "ha", "as", "ou", "io", "le", "ve", "co",
+    "me", "de", "hi", "ri", "ro", "ic", "ne", "ea", "ra", "ce", "li", "ch", "ll", "be", "ma", "si",
+    "om", "ur",
+];
+
+/// Endings that make a long word easy to spot.
+const COMMON_SUFFIXES: &[&str] = &[
+    "ing", "ion", "tion", "ers", "ies", "ed", "ness", "ment", "able", "ally", "ists", "ity", "ous",
+];
+
+/// Number of solutions at (or above) which a board no longer gets harder for having fewer words.
+const MANY_SOLUTIONS: f32 = 80.0;
+
+/// Scores below this are [`Band::Easy`].
+pub const EASY_BELOW: f32 = 40.0;
+/// Scores below this (and at least [`EASY_BELOW`]) are [`Band::Medium`].
+pub const MEDIUM_BELOW: f32 = 60.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Band {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl fmt::Display for Band {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Band::Easy => write!(f, "Easy"),
+            Band::Medium => write!(f, "Medium"),
+            Band::Hard => write!(f, "Hard"),
+        }
+    }
+}
+
+/// How hard a board is, and the measurements that went into it.
+///
+/// Every factor is between 0 (easy) and 1 (hard).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Difficulty {
+    /// Overall score from 0 to 100.
+    pub score: f32,
+    pub band: Band,
+    pub solutions: usize,
+    pub full_words: usize,
+    /// Fewer solutions leave fewer words to find.
+    pub scarcity: f32,
+    /// How unusual the letters of the solutions are, standing in for how rare the words are.
+    pub rarity: f32,
+    /// A single nine-letter word is harder than a choice of several.
+    pub full_word_scarcity: f32,
+    /// How well the easiest nine-letter word hides, by its letter pairs and ending.
+    pub obscurity: f32,
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({:.0})", self.band, self.score)
+    }
+}
+
+/// Estimates how hard `puzzle` is to complete.
+pub fn difficulty(puzzle: &NineP) -> Difficulty {
+    let solutions = puzzle.solve();
+    let full_words = solutions
+        .iter()
+        .filter(|w| puzzle.is_full_word(w))
+        .collect::<Vec<&String>>();
+
+    let scarcity = 1.0 - (solutions.len() as f32 / MANY_SOLUTIONS).min(1.0);
+    let rarity = if solutions.is_empty() {
+        1.0
+    } else {
+        solutions.iter().map(|w| word_rarity(w)).sum::<f32>() / solutions.len() as f32
+    };
+    let full_word_scarcity = match full_words.len() {
+        0 => 1.0,
+        n => 1.0 / n as f32,
+    };
+    let obscurity = 1.0
+        - full_words
+            .iter()
+            .map(|w| findability(w))
+            .fold(0.0, f32::max);
+
+    let score =
+        100.0 * (0.3 * scarcity + 0.25 * rarity + 0.15 * full_word_scarcity + 0.3 * obscurity);
+
+    Difficulty {
+        score,
+        band: band(score),
+        solutions: solutions.len(),
+        full_words: full_words.len(),
+        scarcity,
+        rarity,
+        full_word_scarcity,
+        obscurity,
+    }
+}
+
+pub fn band(score: f32) -> Band {
+    if score < EASY_BELOW {
+        Band::Easy
+    } else if score < MEDIUM_BELOW {
+        Band::Medium
+    } else {
+        Band::Hard
+    }
+}
+
+/// 0 for a word made of the most common letter, approaching 1 for one made of the rarest.
+fn word_rarity(word: &str) -> f32 {
+    let max = LETTER_FREQUENCY.iter().copied().fold(0.0, f32::max);
+    let letters = word.chars().count().max(1) as f32;
+    let commonness = word
+        .chars()
+        .map(|c| match c {
+            'a'..='z' => LETTER_FREQUENCY[c as usize - 'a' as usize],
+            _ => 0.0,
+        })
+        .sum::<f32>()
+        / letters;
+    1.0 - commonness / max
+}
+
+/// Share of the word's letter pairs that are common, with a bonus for a familiar ending.
+fn findability(word: &str) -> f32 {
+    let letters: Vec<char> = word.chars().collect();
+    if letters.len() < 2 {
+        return 0.0;
+    }
+    let common = letters
+        .windows(2)
+        .filter(|pair| COMMON_BIGRAMS.contains(&pair.iter().collect::<String>().as_str()))
+        .count();
+    let mut findability = common as f32 / (letters.len() - 1) as f32;
+    if COMMON_SUFFIXES.iter().any(|s| word.ends_with(s)) {
+        findability += 0.25;
+    }
+    findability.min(1.0)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{init_logger, word_tree::WordTree};
+
+    use super::*;
+
+    #[test]
+    fn bands() {
+        assert_eq!(band(0.0), Band::Easy);
+        assert_eq!(band(EASY_BELOW), Band::Medium);
+        assert_eq!(band(MEDIUM_BELOW), Band::Hard);
+        assert_eq!(band(100.0), Band::Hard);
+    }
+
+    #[test]
+    fn common_words_are_easier() {
+        init_logger();
+        let easy = NineP::new(
+            "reporting".to_string(),
+            WordTree::new(
+                &[
+                    "reporting",
+                    "porting",
+                    "tripe",
+                    "report",
+                    "toper",
+                    "gripe",
+                    "tiger",
+                    "prig",
+                    "trip",
+                    "ring",
+                ]
+                .map(String::from),
+            ),
+        );
+        let hard = NineP::new(
+            "zyxomqubk".to_string(),
+            WordTree::new(&["zyxomqubk".to_string()]),
+        );
+
+        let easy = difficulty(&easy);
+        let hard = difficulty(&hard);
+        assert_eq!(easy.solutions, 10);
+        assert_eq!(easy.full_words, 1);
+        assert!(easy.rarity < hard.rarity);
+        assert!(easy.obscurity < hard.obscurity);
+        assert!(easy.score < hard.score);
+        assert_eq!(hard.band, Band::Hard);
+    }
+}
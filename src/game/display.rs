@@ -0,0 +1,133 @@
+use std::fmt;
+
+use super::NineP;
+
+const HIGHLIGHT_START: &str = "\x1b[1;7m";
+const HIGHLIGHT_END: &str = "\x1b[0m";
+
+/// Draws a [`NineP`] as a 3x3 grid, with the centre letter in the middle cell.
+///
+/// The other letters go round it in board order, so [`NineP::shuffle`] rearranges the outer ring
+/// while the centre stays put.
+pub struct BoardView<'a> {
+    board: &'a NineP,
+    highlight: bool,
+}
+
+impl NineP {
+    /// A grid view of the board. When `highlight` is set the centre letter is picked out with ANSI
+    /// escape codes, which should only be used when writing to a terminal.
+    pub fn render(&self, highlight: bool) -> BoardView<'_> {
+        BoardView {
+            board: self,
+            highlight,
+        }
+    }
+}
+
+impl BoardView<'_> {
+    /// The letters in the order they are drawn, left to right and top to bottom, along with the
+    /// cell holding the centre letter.
+    fn cells(&self) -> (Vec<char>, Option<usize>) {
+        let mut cells: Vec<char> = self.board.letters().chars().collect();
+        let centre = self.board.centre().and_then(|centre| {
+            let i = cells.iter().position(|c| *c == centre)?;
+            cells.remove(i);
+            let middle = cells.len().min(4);
+            cells.insert(middle, centre);
+            Some(middle)
+        });
+        (cells, centre)
+    }
+}
+
+impl fmt::Display for BoardView<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (cells, centre_cell) = self.cells();
+        let rows = cells.len().div_ceil(3).max(1);
+
+        writeln!(f, "+---+---+---+")?;
+        for row in 0..rows {
+            write!(f, "|")?;
+            for column in 0..3 {
+                let i = row * 3 + column;
+                let letter = cells.get(i).map_or(' ', |c| c.to_ascii_uppercase());
+                if self.highlight && Some(i) == centre_cell {
+                    write!(f, "{} {} {}|", HIGHLIGHT_START, letter, HIGHLIGHT_END)?;
+                } else {
+                    write!(f, " {} |", letter)?;
+                }
+            }
+            writeln!(f)?;
+            write!(f, "+---+---+---+")?;
+            if row + 1 < rows {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for NineP {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.render(false).fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{init_logger, word_tree::WordTree};
+
+    use super::*;
+
+    #[test]
+    fn centre_in_middle() {
+        init_logger();
+        let board = NineP::new("aspersion".to_string(), WordTree::default())
+            .with_centre('n')
+            .unwrap();
+        assert_eq!(
+            board.to_string(),
+            "+---+---+---+\n\
+             | A | S | P |\n\
+             +---+---+---+\n\
+             | E | N | R |\n\
+             +---+---+---+\n\
+             | S | I | O |\n\
+             +---+---+---+"
+        );
+        assert!(board
+            .render(true)
+            .to_string()
+            .contains("|\x1b[1;7m N \x1b[0m|"));
+    }
+
+    #[test]
+    fn shuffle_keeps_centre() {
+        init_logger();
+        let mut board = NineP::new("aspersion".to_string(), WordTree::default())
+            .with_centre('a')
+            .unwrap();
+        for seed in 0..5 {
+            board.shuffle(seed);
+            let middle_row = board.to_string().lines().nth(3).unwrap().to_string();
+            assert_eq!(&middle_row[4..9], "| A |");
+        }
+    }
+
+    #[test]
+    fn short_board() {
+        init_logger();
+        let board = NineP::new("eimoprss".to_string(), WordTree::default());
+        assert_eq!(
+            board.render(true).to_string(),
+            "+---+---+---+\n\
+             | E | I | M |\n\
+             +---+---+---+\n\
+             | O | P | R |\n\
+             +---+---+---+\n\
+             | S | S |   |\n\
+             +---+---+---+"
+        );
+    }
+}
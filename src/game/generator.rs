@@ -0,0 +1,403 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::sync::Arc;
+
+use log::{debug, info};
+
+use super::NineP;
+use crate::{rng::Rng, word_tree::WordTree};
+
+/// Number of letters on a generated board.
+pub const BOARD_SIZE: usize = 9;
+
+/// Requirements a generated board has to meet, see [`Generator::generate_with`].
+#[derive(Debug, Clone, Default)]
+pub struct Constraints {
+    single_full_word: bool,
+    word_count: Option<RangeInclusive<usize>>,
+    blocklist: HashSet<String>,
+    common_words: HashSet<String>,
+    min_common_words: usize,
+}
+
+/// Why a candidate board did not meet the [`Constraints`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rejection {
+    /// The board has this many nine-letter solutions rather than exactly one.
+    FullWords(usize),
+    /// The total number of solutions is outside of the allowed range.
+    WordCount(usize),
+    /// These solutions are on the blocklist.
+    Blocked(Vec<String>),
+    /// Only this many of the solutions are common words.
+    TooFewCommonWords(usize),
+}
+
+/// A candidate board that was thrown away.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rejected {
+    pub seed: u64,
+    pub reasons: Vec<Rejection>,
+}
+
+/// Why [`Generator::generate_with`] did not find a board.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GenerateError {
+    /// The word list has no nine-letter words to build a board from.
+    NoWords,
+    /// No boards were tried, as `max_attempts` was 0.
+    NoAttempts,
+    /// No board met the constraints. Every candidate that was tried is listed.
+    Rejected(Vec<Rejected>),
+}
+
+/// A board meeting the [`Constraints`], along with the candidates tried before it.
+pub struct Generated {
+    pub seed: u64,
+    pub puzzle: NineP,
+    pub rejected: Vec<Rejected>,
+}
+
+impl Constraints {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only allow boards with exactly one nine-letter word.
+    pub fn single_full_word(mut self) -> Self {
+        self.single_full_word = true;
+        self
+    }
+
+    pub fn word_count(mut self, range: RangeInclusive<usize>) -> Self {
+        self.word_count = Some(range);
+        self
+    }
+
+    /// Reject boards where any of `words` is a solution.
+    pub fn blocklist<I: IntoIterator<Item = String>>(mut self, words: I) -> Self {
+        self.blocklist.extend(words);
+        self
+    }
+
+    /// Require at least `count` solutions to be in `common_words`.
+    pub fn min_common_words<I: IntoIterator<Item = String>>(
+        mut self,
+        count: usize,
+        common_words: I,
+    ) -> Self {
+        self.min_common_words = count;
+        self.common_words.extend(common_words);
+        self
+    }
+
+    /// Every way `puzzle` falls short, empty if it is acceptable.
+    pub fn check(&self, puzzle: &NineP) -> Vec<Rejection> {
+        let solutions = puzzle.solve();
+        let mut reasons = Vec::new();
+
+        let full_words = solutions.iter().filter(|w| puzzle.is_full_word(w)).count();
+        if self.single_full_word && full_words != 1 {
+            reasons.push(Rejection::FullWords(full_words));
+        }
+
+        if let Some(range) = &self.word_count {
+            if !range.contains(&solutions.len()) {
+                reasons.push(Rejection::WordCount(solutions.len()));
+            }
+        }
+
+        let mut blocked = solutions
+            .iter()
+            .filter(|w| self.blocklist.contains(*w))
+            .cloned()
+            .collect::<Vec<String>>();
+        if !blocked.is_empty() {
+            blocked.sort();
+            reasons.push(Rejection::Blocked(blocked));
+        }
+
+        let common = solutions
+            .iter()
+            .filter(|w| self.common_words.contains(*w))
+            .count();
+        if common < self.min_common_words {
+            reasons.push(Rejection::TooFewCommonWords(common));
+        }
+
+        reasons
+    }
+}
+
+impl Rejection {
+    /// Which constraint was not met, without the details, for counting rejections.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Rejection::FullWords(_) => "nine letter words",
+            Rejection::WordCount(_) => "word count",
+            Rejection::Blocked(_) => "blocked words",
+            Rejection::TooFewCommonWords(_) => "too few common words",
+        }
+    }
+}
+
+/// How many of `rejected` failed each constraint, by [`Rejection::kind`]. A board can fail more
+/// than one.
+pub fn count_reasons(rejected: &[Rejected]) -> BTreeMap<&'static str, usize> {
+    let mut counts = BTreeMap::new();
+    for reason in rejected.iter().flat_map(|r| &r.reasons) {
+        *counts.entry(reason.kind()).or_default() += 1;
+    }
+    counts
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rejection::FullWords(n) => write!(f, "{} nine letter words instead of one", n),
+            Rejection::WordCount(n) => write!(f, "{} words is outside the allowed range", n),
+            Rejection::Blocked(words) => write!(f, "blocked words: {}", words.join(", ")),
+            Rejection::TooFewCommonWords(n) => write!(f, "only {} common words", n),
+        }
+    }
+}
+
+impl fmt::Display for GenerateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GenerateError::NoWords => write!(
+                f,
+                "the word list has no nine letter words to build a board from"
+            ),
+            GenerateError::NoAttempts => write!(f, "no boards were tried"),
+            GenerateError::Rejected(rejected) => write!(
+                f,
+                "no board met the constraints in {} attempts",
+                rejected.len()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GenerateError {}
+
+impl fmt::Display for Rejected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "seed {}: ", self.seed)?;
+        for (i, reason) in self.reasons.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{}", reason)?;
+        }
+        Ok(())
+    }
+}
+
+/// Builds [`NineP`] boards from the nine-letter words in a [`WordTree`].
+pub struct Generator {
+    word_tree: Arc<WordTree>,
+    nine_letter_words: Vec<String>,
+}
+
+impl Generator {
+    pub fn new(word_tree: impl Into<Arc<WordTree>>) -> Self {
+        let word_tree = word_tree.into();
+        let nine_letter_words = word_tree
+            .get_words()
+            .into_iter()
+            .filter(|w| w.chars().count() == BOARD_SIZE)
+            .collect::<Vec<String>>();
+        info!(
+            "Found {} nine letter words to build boards from",
+            nine_letter_words.len()
+        );
+
+        Generator {
+            word_tree,
+            nine_letter_words,
+        }
+    }
+
+    pub fn nine_letter_words(&self) -> &[String] {
+        &self.nine_letter_words
+    }
+
+    /// Picks a nine-letter word, shuffles its letters and picks a centre letter.
+    ///
+    /// The same seed (with the same word list) always gives the same board. Returns `None` if the
+    /// word list has no nine-letter words.
+    pub fn generate(&self, seed: u64) -> Option<NineP> {
+        if self.nine_letter_words.is_empty() {
+            return None;
+        }
+
+        let mut rng = Rng::new(seed);
+        let word = &self.nine_letter_words[rng.below(self.nine_letter_words.len())];
+        debug!("Seed {} picked '{}'", seed, word);
+
+        Some(self.board_from_word(word, &mut rng))
+    }
+
+    /// Tries `seed`, `seed + 1`, ... until a board meets `constraints`, giving up after
+    /// `max_attempts` boards.
+    pub fn generate_with(
+        &self,
+        seed: u64,
+        constraints: &Constraints,
+        max_attempts: usize,
+    ) -> Result<Generated, GenerateError> {
+        if self.nine_letter_words.is_empty() {
+            return Err(GenerateError::NoWords);
+        }
+        if max_attempts == 0 {
+            return Err(GenerateError::NoAttempts);
+        }
+
+        let mut rejected = Vec::new();
+        for seed in (0..max_attempts as u64).map(|i| seed.wrapping_add(i)) {
+            let puzzle = self
+                .generate(seed)
+                .expect("there are nine letter words to choose from");
+
+            let reasons = constraints.check(&puzzle);
+            if reasons.is_empty() {
+                info!(
+                    "Seed {} met the constraints after {} rejections",
+                    seed,
+                    rejected.len()
+                );
+                return Ok(Generated {
+                    seed,
+                    puzzle,
+                    rejected,
+                });
+            }
+
+            let rejection = Rejected { seed, reasons };
+            debug!("Rejected {}", rejection);
+            rejected.push(rejection);
+        }
+
+        Err(GenerateError::Rejected(rejected))
+    }
+
+    pub(super) fn board_from_word(&self, word: &str, rng: &mut Rng) -> NineP {
+        let mut letters: Vec<char> = word.chars().collect();
+        rng.shuffle(&mut letters);
+        let centre = letters[rng.below(letters.len())];
+
+        NineP::new(letters.into_iter().collect(), self.word_tree.clone())
+            .with_centre(centre)
+            .expect("the centre is one of the board's letters")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::init_logger;
+
+    use super::*;
+
+    fn generator() -> Generator {
+        let words = [
+            "aspersion",
+            "education",
+            "orientals",
+            "relations",
+            "prison",
+            "person",
+            "cation",
+            "auction",
+            "tonal",
+        ]
+        .map(String::from);
+        Generator::new(WordTree::new(&words))
+    }
+
+    #[test]
+    fn same_seed_same_board() {
+        init_logger();
+        let generator = generator();
+        assert_eq!(generator.nine_letter_words().len(), 4);
+
+        for seed in 0..20 {
+            let a = generator.generate(seed).unwrap();
+            let b = generator.generate(seed).unwrap();
+            assert_eq!(a.letters(), b.letters());
+            assert_eq!(a.centre(), b.centre());
+
+            let centre = a.centre().unwrap();
+            assert!(a.letters().contains(centre));
+            assert!(a.solve().iter().all(|w| w.contains(centre)));
+            assert!(a.solve().iter().any(|w| a.is_full_word(w)));
+        }
+    }
+
+    #[test]
+    fn seeds_vary_boards() {
+        init_logger();
+        let generator = generator();
+        let boards = (0..20)
+            .map(|seed| generator.generate(seed).unwrap().letters().to_string())
+            .collect::<std::collections::HashSet<String>>();
+        assert!(boards.len() > 1);
+    }
+
+    #[test]
+    fn no_nine_letter_words() {
+        init_logger();
+        let generator = Generator::new(WordTree::new(&["tonal".to_string()]));
+        assert!(generator.generate(1).is_none());
+        assert_eq!(
+            generator
+                .generate_with(1, &Constraints::new(), 10)
+                .err()
+                .unwrap(),
+            GenerateError::NoWords
+        );
+    }
+
+    #[test]
+    fn constraints() {
+        init_logger();
+        let generator = generator();
+        let constraints = Constraints::new()
+            .single_full_word()
+            .word_count(2..=10)
+            .blocklist(["person".to_string()]);
+
+        let generated = generator.generate_with(0, &constraints, 100).unwrap();
+        assert!(constraints.check(&generated.puzzle).is_empty());
+        assert!(!generated.puzzle.solve().contains(&"person".to_string()));
+        for rejected in &generated.rejected {
+            assert!(!rejected.reasons.is_empty());
+            let puzzle = generator.generate(rejected.seed).unwrap();
+            assert_eq!(constraints.check(&puzzle), rejected.reasons);
+        }
+
+        let impossible = Constraints::new().min_common_words(1, ["zzz".to_string()]);
+        let Some(GenerateError::Rejected(rejected)) =
+            generator.generate_with(5, &impossible, 3).err()
+        else {
+            panic!("expected every board to be rejected");
+        };
+        assert_eq!(
+            rejected.iter().map(|r| r.seed).collect::<Vec<u64>>(),
+            [5, 6, 7]
+        );
+        assert!(rejected
+            .iter()
+            .all(|r| r.reasons == [Rejection::TooFewCommonWords(0)]));
+        assert_eq!(
+            count_reasons(&rejected).into_iter().collect::<Vec<_>>(),
+            [("too few common words", 3)]
+        );
+
+        assert_eq!(
+            generator.generate_with(0, &constraints, 0).err().unwrap(),
+            GenerateError::NoAttempts
+        );
+    }
+}
@@ -0,0 +1,358 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+use log::debug;
+
+use super::{Game, InvalidGuess};
+use crate::word_tree::{LetterNode, WordTree};
+
+/// Shortest word counted by default, as in Boggle.
+pub const DEFAULT_MIN_LENGTH: usize = 3;
+
+/// A square letter grid where words are made by moving between neighbouring cells (including
+/// diagonals), using each cell at most once.
+pub struct Grid {
+    size: usize,
+    cells: Vec<char>,
+    word_tree: Arc<WordTree>,
+    min_length: usize,
+}
+
+/// A word found on a [`Grid`], with the `(row, column)` of each cell it passes through.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GridWord {
+    pub word: String,
+    pub path: Vec<(usize, usize)>,
+}
+
+/// Reasons letters can not be made into a [`Grid`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidGrid {
+    /// Only 3x3, 4x4 and 5x5 grids are supported, this many letters were given.
+    Size(usize),
+    /// Grids may only hold the letters `a` to `z`.
+    Letter(char),
+}
+
+impl fmt::Display for InvalidGrid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidGrid::Size(n) => write!(f, "{} letters can not fill a 3x3, 4x4 or 5x5 grid", n),
+            InvalidGrid::Letter(c) => write!(f, "'{}' can not be placed on a grid", c),
+        }
+    }
+}
+
+impl std::error::Error for InvalidGrid {}
+
+impl Grid {
+    /// Builds a grid from its letters, row by row. The size is worked out from the number of
+    /// letters.
+    pub fn new(letters: &str, word_tree: impl Into<Arc<WordTree>>) -> Result<Self, InvalidGrid> {
+        let cells: Vec<char> = letters.chars().collect();
+        let size = match cells.len() {
+            9 => 3,
+            16 => 4,
+            25 => 5,
+            n => return Err(InvalidGrid::Size(n)),
+        };
+        if let Some(c) = cells.iter().find(|c| !c.is_ascii_lowercase()) {
+            return Err(InvalidGrid::Letter(*c));
+        }
+
+        Ok(Grid {
+            size,
+            cells,
+            word_tree: word_tree.into(),
+            min_length: DEFAULT_MIN_LENGTH,
+        })
+    }
+
+    pub fn with_min_length(mut self, min_length: usize) -> Self {
+        self.min_length = min_length;
+        self
+    }
+
+    /// Width (and height) of the grid.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn letter(&self, row: usize, column: usize) -> char {
+        self.cells[row * self.size + column]
+    }
+
+    /// Every word on the grid, A-Z, each with the first path found for it.
+    pub fn solve(&self) -> Vec<GridWord> {
+        let mut found = BTreeMap::new();
+        let mut visited = vec![false; self.cells.len()];
+        let mut word = String::new();
+        let mut path = Vec::new();
+
+        for start in 0..self.cells.len() {
+            if let Some(node) = self.word_tree.root().child(self.cells[start]) {
+                self.walk(node, start, &mut visited, &mut word, &mut path, &mut found);
+            }
+        }
+        debug!("Found {} words on the grid", found.len());
+
+        found
+            .into_iter()
+            .map(|(word, path)| GridWord {
+                word,
+                path: path
+                    .into_iter()
+                    .map(|i| (i / self.size, i % self.size))
+                    .collect(),
+            })
+            .collect()
+    }
+
+    /// The cells, as `(row, column)`, that spell `word` without using a cell twice, or `None` if
+    /// there is no such path.
+    pub fn find_path(&self, word: &str) -> Option<Vec<(usize, usize)>> {
+        let letters = word.chars().collect::<Vec<char>>();
+        let first = *letters.first()?;
+        let mut visited = vec![false; self.cells.len()];
+        let mut path = Vec::new();
+        (0..self.cells.len())
+            .filter(|&start| self.cells[start] == first)
+            .find(|&start| self.trace(&letters, start, &mut visited, &mut path))?;
+        Some(
+            path.into_iter()
+                .map(|i| (i / self.size, i % self.size))
+                .collect(),
+        )
+    }
+
+    /// Boggle scoring: one point for three or four letters, two for five, three for six, five for
+    /// seven and eleven for anything longer.
+    pub fn score_word(&self, word: &str) -> u32 {
+        match word.chars().count() {
+            0..=4 => 1,
+            5 => 2,
+            6 => 3,
+            7 => 5,
+            _ => 11,
+        }
+    }
+
+    /// Depth first search of the grid, following `node` down the tree so that dead ends are cut
+    /// off as soon as no word starts with the letters so far.
+    fn walk(
+        &self,
+        node: &LetterNode,
+        cell: usize,
+        visited: &mut [bool],
+        word: &mut String,
+        path: &mut Vec<usize>,
+        found: &mut BTreeMap<String, Vec<usize>>,
+    ) {
+        visited[cell] = true;
+        word.push(self.cells[cell]);
+        path.push(cell);
+
+        if node.is_terminator() && word.len() >= self.min_length && !found.contains_key(word) {
+            found.insert(word.clone(), path.clone());
+        }
+
+        for next in self.neighbours(cell) {
+            if visited[next] {
+                continue;
+            }
+            if let Some(child) = node.child(self.cells[next]) {
+                self.walk(child, next, visited, word, path, found);
+            }
+        }
+
+        visited[cell] = false;
+        word.pop();
+        path.pop();
+    }
+
+    /// Whether the rest of `letters` can be spelt on from `cell`, which holds the first of them,
+    /// leaving the cells used in `path` if so.
+    fn trace(
+        &self,
+        letters: &[char],
+        cell: usize,
+        visited: &mut [bool],
+        path: &mut Vec<usize>,
+    ) -> bool {
+        visited[cell] = true;
+        path.push(cell);
+        if letters.len() == 1
+            || self.neighbours(cell).any(|next| {
+                !visited[next]
+                    && self.cells[next] == letters[1]
+                    && self.trace(&letters[1..], next, visited, path)
+            })
+        {
+            return true;
+        }
+        visited[cell] = false;
+        path.pop();
+        false
+    }
+
+    fn neighbours(&self, cell: usize) -> impl Iterator<Item = usize> + '_ {
+        let (row, column) = ((cell / self.size) as isize, (cell % self.size) as isize);
+        let size = self.size as isize;
+        (-1..=1)
+            .flat_map(move |dr| (-1..=1).map(move |dc| (row + dr, column + dc)))
+            .filter(move |&(r, c)| {
+                (r, c) != (row, column) && (0..size).contains(&r) && (0..size).contains(&c)
+            })
+            .map(move |(r, c)| (r * size + c) as usize)
+    }
+}
+
+impl fmt::Display for Grid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, row) in self.cells.chunks(self.size).enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            let row = row
+                .iter()
+                .map(|c| c.to_ascii_uppercase().to_string())
+                .collect::<Vec<String>>();
+            write!(f, "{}", row.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+impl Game for Grid {
+    fn solve(&self) -> Vec<String> {
+        Grid::solve(self).into_iter().map(|w| w.word).collect()
+    }
+
+    fn check_guess(&self, guess: &str) -> Result<(), InvalidGuess> {
+        if guess.chars().count() < self.min_length {
+            return Err(InvalidGuess::TooShort);
+        }
+        let mut letters = self.cells.clone();
+        for c in guess.chars() {
+            match letters.iter().position(|l| *l == c) {
+                Some(i) => letters.swap_remove(i),
+                None => return Err(InvalidGuess::UnavailableLetters),
+            };
+        }
+        if !self.word_tree.contains(guess) {
+            return Err(InvalidGuess::NotAWord);
+        }
+        match self.find_path(guess) {
+            Some(_) => Ok(()),
+            None => Err(InvalidGuess::NotConnected),
+        }
+    }
+
+    fn score(&self, word: &str) -> u32 {
+        self.score_word(word)
+    }
+
+    fn describe_board(&self) -> String {
+        self.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::init_logger;
+
+    use super::*;
+
+    fn is_adjacent(a: (usize, usize), b: (usize, usize)) -> bool {
+        a != b && a.0.abs_diff(b.0) <= 1 && a.1.abs_diff(b.1) <= 1
+    }
+
+    #[test]
+    fn paths() {
+        init_logger();
+        // c a t
+        // o r s
+        // d e n
+        let words = [
+            "cat", "cats", "car", "cart", "cod", "code", "coder", "tac", "scat", "den", "ease",
+        ]
+        .map(String::from);
+        let grid = Grid::new("catorsden", WordTree::new(&words)).unwrap();
+        assert_eq!(grid.size(), 3);
+        assert_eq!(grid.letter(1, 2), 's');
+
+        let found = grid.solve();
+        assert_eq!(
+            found.iter().map(|w| w.word.as_str()).collect::<Vec<&str>>(),
+            ["car", "cart", "cat", "cats", "cod", "code", "coder", "den", "tac"]
+        );
+        for word in &found {
+            assert_eq!(word.path.len(), word.word.len());
+            for (i, (row, column)) in word.path.iter().enumerate() {
+                assert_eq!(
+                    grid.letter(*row, *column),
+                    word.word.chars().nth(i).unwrap()
+                );
+            }
+            assert!(word.path.windows(2).all(|p| is_adjacent(p[0], p[1])));
+        }
+        assert_eq!(found[2].path, [(0, 0), (0, 1), (0, 2)]);
+    }
+
+    #[test]
+    fn cells_are_not_reused() {
+        init_logger();
+        let words = ["aba", "abab", "ababa"].map(String::from);
+        let grid = Grid::new("abxxxxxxx", WordTree::new(&words)).unwrap();
+        assert!(grid.solve().is_empty());
+
+        let grid = Grid::new("abaxxxxxx", WordTree::new(&words)).unwrap();
+        assert_eq!(grid.solve().len(), 1);
+    }
+
+    #[test]
+    fn sizes() {
+        init_logger();
+        assert!(Grid::new(&"a".repeat(16), WordTree::default()).is_ok());
+        assert!(Grid::new(&"a".repeat(25), WordTree::default()).is_ok());
+        assert_eq!(
+            Grid::new("abc", WordTree::default()).err(),
+            Some(InvalidGrid::Size(3))
+        );
+        assert_eq!(
+            Grid::new("abcdefgh1", WordTree::default()).err(),
+            Some(InvalidGrid::Letter('1'))
+        );
+    }
+
+    #[test]
+    fn guesses() {
+        init_logger();
+        let words = ["cat", "cast", "dent", "tacos", "node"].map(String::from);
+        // c a t
+        // o r s
+        // d e n
+        let grid = Grid::new("catorsden", WordTree::new(&words)).unwrap();
+
+        assert_eq!(grid.check_guess("cast"), Ok(()));
+        assert_eq!(
+            grid.find_path("cast"),
+            Some(vec![(0, 0), (0, 1), (1, 2), (0, 2)])
+        );
+        assert_eq!(grid.check_guess("ca"), Err(InvalidGuess::TooShort));
+        assert_eq!(grid.check_guess("cats"), Err(InvalidGuess::NotAWord));
+        assert_eq!(grid.check_guess("tacos"), Err(InvalidGuess::NotConnected));
+        assert_eq!(grid.check_guess("node"), Err(InvalidGuess::NotConnected));
+        assert_eq!(
+            grid.check_guess("zone"),
+            Err(InvalidGuess::UnavailableLetters)
+        );
+        // Only one 't' on the board
+        assert_eq!(
+            grid.check_guess("tent"),
+            Err(InvalidGuess::UnavailableLetters)
+        );
+        assert_eq!(grid.find_path(""), None);
+    }
+}
@@ -0,0 +1,186 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Hints about a set of words that have not been found yet.
+///
+/// Built from the results of [`crate::word_tree::WordTree::solve`], either for a whole board
+/// ([`super::NineP::hints`]) or for what is left in a session
+/// ([`super::session::GameSession::hints`]).
+pub struct Hints {
+    words: Vec<String>,
+}
+
+/// Number of words, by first letter and length.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HintGrid {
+    lengths: Vec<usize>,
+    counts: BTreeMap<char, BTreeMap<usize, usize>>,
+}
+
+/// Number of words starting with each two-letter prefix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrefixCounts(pub BTreeMap<String, usize>);
+
+impl Hints {
+    pub fn new(mut words: Vec<String>) -> Self {
+        words.sort();
+        words.dedup();
+        Hints { words }
+    }
+
+    pub fn words_left(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn grid(&self) -> HintGrid {
+        let mut lengths = Vec::new();
+        let mut counts: BTreeMap<char, BTreeMap<usize, usize>> = BTreeMap::new();
+        for word in &self.words {
+            let Some(first) = word.chars().next() else {
+                continue;
+            };
+            let length = word.chars().count();
+            if !lengths.contains(&length) {
+                lengths.push(length);
+            }
+            *counts.entry(first).or_default().entry(length).or_default() += 1;
+        }
+        lengths.sort_unstable();
+
+        HintGrid { lengths, counts }
+    }
+
+    pub fn prefix_counts(&self) -> PrefixCounts {
+        let mut counts = BTreeMap::new();
+        for word in &self.words {
+            let prefix: String = word.chars().take(2).collect();
+            *counts.entry(prefix).or_default() += 1;
+        }
+        PrefixCounts(counts)
+    }
+
+    /// The first `letters` letters of the `index`th word (A-Z), never giving away the whole word.
+    pub fn reveal(&self, index: usize, letters: usize) -> Option<String> {
+        let word = self.words.get(index)?;
+        let length = word.chars().count();
+        Some(
+            word.chars()
+                .take(letters.min(length.saturating_sub(1)))
+                .collect(),
+        )
+    }
+}
+
+impl HintGrid {
+    /// How many words start with `letter` and are `length` letters long.
+    pub fn count(&self, letter: char, length: usize) -> usize {
+        self.counts
+            .get(&letter)
+            .and_then(|row| row.get(&length))
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+impl fmt::Display for HintGrid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "  ")?;
+        for length in &self.lengths {
+            write!(f, "{:>4}", length)?;
+        }
+        writeln!(f, "{:>4}", "Σ")?;
+
+        let mut column_totals = vec![0; self.lengths.len()];
+        for (letter, row) in &self.counts {
+            write!(f, "{} ", letter)?;
+            for (i, length) in self.lengths.iter().enumerate() {
+                match row.get(length) {
+                    Some(count) => {
+                        column_totals[i] += count;
+                        write!(f, "{:>4}", count)?
+                    }
+                    None => write!(f, "{:>4}", "-")?,
+                }
+            }
+            writeln!(f, "{:>4}", row.values().sum::<usize>())?;
+        }
+
+        write!(f, "Σ ")?;
+        for total in &column_totals {
+            write!(f, "{:>4}", total)?;
+        }
+        write!(f, "{:>4}", column_totals.iter().sum::<usize>())
+    }
+}
+
+impl fmt::Display for PrefixCounts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut current = None;
+        for (prefix, count) in &self.0 {
+            let first = prefix.chars().next();
+            if current.is_some() && current != first {
+                writeln!(f)?;
+            } else if current.is_some() {
+                write!(f, " ")?;
+            }
+            current = first;
+            write!(f, "{}-{}", prefix.to_uppercase(), count)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::init_logger;
+
+    use super::*;
+
+    fn hints() -> Hints {
+        Hints::new(
+            ["tame", "team", "meat", "mate", "steam", "tames", "seat"]
+                .map(String::from)
+                .to_vec(),
+        )
+    }
+
+    #[test]
+    fn grid() {
+        init_logger();
+        let grid = hints().grid();
+        assert_eq!(grid.count('t', 4), 2);
+        assert_eq!(grid.count('t', 5), 1);
+        assert_eq!(grid.count('m', 4), 2);
+        assert_eq!(grid.count('s', 5), 1);
+        assert_eq!(grid.count('z', 4), 0);
+        assert_eq!(
+            grid.to_string(),
+            "     4   5   Σ\nm    2   -   2\ns    1   1   2\nt    2   1   3\nΣ    5   2   7"
+        );
+    }
+
+    #[test]
+    fn prefixes() {
+        init_logger();
+        let hints = hints();
+        assert_eq!(hints.words_left(), 7);
+        assert_eq!(
+            hints.prefix_counts().to_string(),
+            "MA-1 ME-1\nSE-1 ST-1\nTA-2 TE-1"
+        );
+    }
+
+    #[test]
+    fn reveal() {
+        init_logger();
+        let hints = hints();
+        assert_eq!(hints.reveal(0, 1), Some("m".to_string()));
+        assert_eq!(hints.reveal(4, 3), Some("tam".to_string()));
+        assert_eq!(hints.reveal(4, 10), Some("tam".to_string()));
+        assert_eq!(hints.reveal(7, 1), None);
+
+        // A tree loaded without a filter can hold the empty word
+        let hints = Hints::new(vec![String::new()]);
+        assert_eq!(hints.reveal(0, 1), Some(String::new()));
+    }
+}
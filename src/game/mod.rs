@@ -1,22 +1,186 @@
-use crate::word_tree::{self, WordTree};
+use crate::{rng::Rng, word_tree::WordTree};
 
-use log::info;
+use std::fmt;
+use std::sync::Arc;
 
+use hints::Hints;
+
+pub mod countdown;
+pub mod daily;
+pub mod difficulty;
+pub mod display;
+pub mod generator;
+pub mod grid;
+pub mod hints;
+pub mod multiplayer;
+pub mod save;
+pub mod scrabble;
+pub mod session;
+pub mod spelling_bee;
+pub mod timer;
+
+#[derive(Clone)]
 pub struct NineP {
     chars: String,
-    word_tree: WordTree,
+    centre: Option<char>,
+    word_tree: Arc<WordTree>,
+}
+
+/// Reasons a guess can be turned down by [`NineP::check_guess`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidGuess {
+    /// The guess needs letters that are not on the board (or more copies of one than there are).
+    UnavailableLetters,
+    /// The guess does not use the centre letter.
+    MissingCentre,
+    /// The guess can be spelt from the board, but is not in the word list.
+    NotAWord,
+    /// The guess is shorter than the game allows.
+    TooShort,
+    /// The letters are on the board, but not next to each other in the right order.
+    NotConnected,
+}
+
+impl fmt::Display for InvalidGuess {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidGuess::UnavailableLetters => write!(f, "uses letters that are not on the board"),
+            InvalidGuess::MissingCentre => write!(f, "does not use the centre letter"),
+            InvalidGuess::NotAWord => write!(f, "is not in the word list"),
+            InvalidGuess::TooShort => write!(f, "is too short"),
+            InvalidGuess::NotConnected => write!(f, "can not be traced across the board"),
+        }
+    }
+}
+
+/// A centre letter that is not one of the board's letters, from [`NineP::with_centre`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidCentre(pub char);
+
+impl fmt::Display for InvalidCentre {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "centre letter '{}' is not on the board", self.0)
+    }
+}
+
+impl std::error::Error for InvalidCentre {}
+
+/// What front-ends need to run any of the word games: the answers, whether a guess counts, what
+/// it is worth and how to show the board.
+pub trait Game {
+    /// Every word that can be found on the board.
+    fn solve(&self) -> Vec<String>;
+
+    fn check_guess(&self, guess: &str) -> Result<(), InvalidGuess>;
+
+    /// Points for a word, assuming it passes [`Game::check_guess`].
+    fn score(&self, word: &str) -> u32;
+
+    /// The board as text, ready to print.
+    fn describe_board(&self) -> String;
 }
 
 impl NineP {
-    pub fn new(board: String, word_tree: WordTree) -> Self {
+    pub fn new(board: String, word_tree: impl Into<Arc<WordTree>>) -> Self {
         NineP {
             chars: board,
-            word_tree,
+            centre: None,
+            word_tree: word_tree.into(),
         }
     }
 
+    /// Requires every solution to use `centre`, which must be one of the board's letters. Upper
+    /// case letters are lowercased first.
+    pub fn with_centre(mut self, centre: char) -> Result<Self, InvalidCentre> {
+        let centre = centre.to_ascii_lowercase();
+        if !self.chars.contains(centre) {
+            return Err(InvalidCentre(centre));
+        }
+        self.centre = Some(centre);
+        Ok(self)
+    }
+
+    pub fn letters(&self) -> &str {
+        &self.chars
+    }
+
+    pub fn centre(&self) -> Option<char> {
+        self.centre
+    }
+
+    pub fn word_tree(&self) -> &Arc<WordTree> {
+        &self.word_tree
+    }
+
     pub fn solve(&self) -> Vec<String> {
-        self.word_tree.solve(&self.chars)
+        let mut words = self.word_tree.solve(&self.chars);
+        if let Some(centre) = self.centre {
+            words.retain(|w| w.contains(centre));
+        }
+        words
+    }
+
+    /// Rearranges the letters on the board, without changing the puzzle. The centre letter keeps
+    /// its place when drawn (see [`display::BoardView`]), so this reshuffles the outer ring.
+    pub fn shuffle(&mut self, seed: u64) {
+        let mut letters: Vec<char> = self.chars.chars().collect();
+        Rng::new(seed).shuffle(&mut letters);
+        self.chars = letters.into_iter().collect();
+    }
+
+    pub fn hints(&self) -> Hints {
+        Hints::new(self.solve())
+    }
+
+    /// Checks that `word` can be spelt with the letters on the board and is in the word list.
+    pub fn check_guess(&self, word: &str) -> Result<(), InvalidGuess> {
+        let mut available: Vec<char> = self.chars.chars().collect();
+        for c in word.chars() {
+            match available.iter().position(|a| *a == c) {
+                Some(i) => {
+                    available.swap_remove(i);
+                }
+                None => return Err(InvalidGuess::UnavailableLetters),
+            }
+        }
+
+        if self.centre.is_some_and(|c| !word.contains(c)) {
+            return Err(InvalidGuess::MissingCentre);
+        }
+
+        if self.word_tree.contains(word) {
+            Ok(())
+        } else {
+            Err(InvalidGuess::NotAWord)
+        }
+    }
+
+    /// Whether `word` uses every letter on the board, i.e. it is the nine-letter word.
+    pub fn is_full_word(&self, word: &str) -> bool {
+        word.chars().count() == self.chars.chars().count()
+    }
+
+    /// Points awarded for finding `word`: one point per letter.
+    pub fn score_word(&self, word: &str) -> u32 {
+        word.chars().count() as u32
+    }
+}
+
+impl Game for NineP {
+    fn solve(&self) -> Vec<String> {
+        NineP::solve(self)
+    }
+
+    fn check_guess(&self, guess: &str) -> Result<(), InvalidGuess> {
+        NineP::check_guess(self, guess)
+    }
+
+    fn score(&self, word: &str) -> u32 {
+        self.score_word(word)
+    }
+
+    fn describe_board(&self) -> String {
+        self.to_string()
     }
 }
 
@@ -27,12 +191,75 @@ mod test {
     use super::*;
     use rayon::prelude::*;
 
+    #[test]
+    fn centre_letter() {
+        init_logger();
+        let words = ["person", "prison", "prions", "rosin", "spire"].map(String::from);
+        let board = NineP::new("aspersion".to_string(), WordTree::new(&words))
+            .with_centre('N')
+            .unwrap();
+        assert_eq!(board.centre(), Some('n'));
+
+        let mut solutions = board.solve();
+        solutions.par_sort();
+        assert_eq!(solutions, ["person", "prions", "prison", "rosin"]);
+        assert_eq!(board.check_guess("spire"), Err(InvalidGuess::MissingCentre));
+        assert_eq!(board.check_guess("rosin"), Ok(()));
+
+        let error = NineP::new("aspersion".to_string(), WordTree::new(&words))
+            .with_centre('k')
+            .map(|_| ())
+            .unwrap_err();
+        assert_eq!(error, InvalidCentre('k'));
+    }
+
+    #[test]
+    fn games() {
+        init_logger();
+        let words = ["person", "prison", "prions", "rosin", "spire", "aspersion"].map(String::from);
+        let tree = Arc::new(WordTree::new(&words));
+        let games: Vec<Box<dyn Game>> = vec![
+            Box::new(NineP::new("aspersion".to_string(), tree.clone())),
+            Box::new(grid::Grid::new("prisnoaex", tree.clone()).unwrap()),
+            Box::new(spelling_bee::SpellingBee::new("eioprsn", 'r', tree).unwrap()),
+        ];
+
+        for game in &games {
+            let words = game.solve();
+            assert!(!words.is_empty(), "{}", game.describe_board());
+            for word in &words {
+                assert_eq!(game.check_guess(word), Ok(()));
+                assert!(game.score(word) > 0);
+            }
+            assert!(game.check_guess("zzzzz").is_err());
+        }
+    }
+
+    #[test]
+    fn shuffle() {
+        init_logger();
+        let words = ["person", "prison", "prions", "rosin", "spire"].map(String::from);
+        let mut board = NineP::new("aspersion".to_string(), WordTree::new(&words));
+        let mut before = board.solve();
+        before.par_sort();
+
+        board.shuffle(3);
+        let mut letters = board.letters().chars().collect::<Vec<char>>();
+        letters.sort();
+        assert_eq!(letters.into_iter().collect::<String>(), "aeinoprss");
+
+        let mut after = board.solve();
+        after.par_sort();
+        assert_eq!(before, after);
+    }
+
     #[test]
     fn level_one() {
         init_logger();
         let board = NineP {
             chars: "eimoprss".to_string(),
-            word_tree: load_9p_like_words("words_eng.txt").unwrap(),
+            centre: None,
+            word_tree: load_9p_like_words("words_eng.txt").unwrap().into(),
         };
 
         let mut words = board.solve();
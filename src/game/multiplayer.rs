@@ -0,0 +1,214 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use super::session::{GameSession, Guess};
+use super::NineP;
+
+/// What happens to words that more than one player found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SharedWords {
+    /// Nobody scores them, as in Boggle.
+    #[default]
+    Cancelled,
+    /// Their points are divided between everyone who found them, rounding down.
+    Split,
+}
+
+/// Several players on the same board, each keeping their own list of found words.
+pub struct Match {
+    puzzle: NineP,
+    players: Vec<(String, GameSession)>,
+    shared_words: SharedWords,
+}
+
+/// One player's result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Standing {
+    pub name: String,
+    pub score: u32,
+    /// Words nobody else found.
+    pub unique: usize,
+    /// Words at least one other player also found.
+    pub shared: usize,
+}
+
+/// Final results, best first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Standings(pub Vec<Standing>);
+
+impl Match {
+    pub fn new(puzzle: NineP) -> Self {
+        Match {
+            puzzle,
+            players: Vec::new(),
+            shared_words: SharedWords::default(),
+        }
+    }
+
+    pub fn with_shared_words(mut self, shared_words: SharedWords) -> Self {
+        self.shared_words = shared_words;
+        self
+    }
+
+    pub fn puzzle(&self) -> &NineP {
+        &self.puzzle
+    }
+
+    /// Adds a player, returning the index to make their guesses with.
+    pub fn add_player(&mut self, name: &str) -> usize {
+        self.players
+            .push((name.to_string(), GameSession::new(self.puzzle.clone())));
+        self.players.len() - 1
+    }
+
+    /// Each player's name and game.
+    pub fn players(&self) -> impl Iterator<Item = (&str, &GameSession)> {
+        self.players.iter().map(|(name, s)| (name.as_str(), s))
+    }
+
+    /// Checks a guess for `player`, see [`GameSession::guess`].
+    ///
+    /// # Panics
+    ///
+    /// If `player` was not returned by [`Self::add_player`].
+    pub fn guess(&mut self, player: usize, word: &str) -> Guess {
+        self.players[player].1.guess(word)
+    }
+
+    pub fn standings(&self) -> Standings {
+        let mut finders: HashMap<&str, u32> = HashMap::new();
+        for (_, session) in &self.players {
+            for word in session.found_words() {
+                *finders.entry(word).or_default() += 1;
+            }
+        }
+
+        let mut standings = self
+            .players
+            .iter()
+            .map(|(name, session)| {
+                let mut standing = Standing {
+                    name: name.clone(),
+                    score: 0,
+                    unique: 0,
+                    shared: 0,
+                };
+                for word in session.found_words() {
+                    let points = self.puzzle.score_word(word);
+                    match (finders[word.as_str()], self.shared_words) {
+                        (1, _) => {
+                            standing.unique += 1;
+                            standing.score += points;
+                        }
+                        (_, SharedWords::Cancelled) => standing.shared += 1,
+                        (n, SharedWords::Split) => {
+                            standing.shared += 1;
+                            standing.score += points / n;
+                        }
+                    }
+                }
+                standing
+            })
+            .collect::<Vec<Standing>>();
+
+        standings.sort_by(|a, b| {
+            b.score
+                .cmp(&a.score)
+                .then(b.unique.cmp(&a.unique))
+                .then(a.name.cmp(&b.name))
+        });
+        Standings(standings)
+    }
+}
+
+impl fmt::Display for Standings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .0
+            .iter()
+            .map(|s| s.name.chars().count())
+            .chain(["Player".len()])
+            .max()
+            .unwrap_or_default();
+
+        write!(
+            f,
+            "{:>2}  {:<width$}  {:>5}  {:>6}  {:>6}",
+            "#", "Player", "Score", "Unique", "Shared"
+        )?;
+        for (i, standing) in self.0.iter().enumerate() {
+            write!(
+                f,
+                "\n{:>2}  {:<width$}  {:>5}  {:>6}  {:>6}",
+                i + 1,
+                standing.name,
+                standing.score,
+                standing.unique,
+                standing.shared
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{init_logger, word_tree::WordTree};
+
+    use super::*;
+
+    fn game(shared_words: SharedWords) -> Match {
+        let words = ["tame", "team", "meat", "mate", "steam", "tames", "seat"].map(String::from);
+        let mut game = Match::new(NineP::new("mates".to_string(), WordTree::new(&words)))
+            .with_shared_words(shared_words);
+
+        let alice = game.add_player("alice");
+        let bob = game.add_player("bob");
+        for word in ["team", "steam", "seat"] {
+            game.guess(alice, word);
+        }
+        for word in ["team", "meat", "mate", "tame"] {
+            game.guess(bob, word);
+        }
+        assert_eq!(game.guess(bob, "team"), Guess::AlreadyFound);
+        game
+    }
+
+    #[test]
+    fn cancelled() {
+        init_logger();
+        let standings = game(SharedWords::Cancelled).standings();
+        assert_eq!(
+            standings.0,
+            [
+                Standing {
+                    name: "bob".to_string(),
+                    score: 12,
+                    unique: 3,
+                    shared: 1,
+                },
+                Standing {
+                    name: "alice".to_string(),
+                    score: 9,
+                    unique: 2,
+                    shared: 1,
+                },
+            ]
+        );
+        assert_eq!(
+            standings.to_string(),
+            " #  Player  Score  Unique  Shared\n \
+              1  bob        12       3       1\n \
+              2  alice       9       2       1"
+        );
+    }
+
+    #[test]
+    fn split() {
+        init_logger();
+        let standings = game(SharedWords::Split).standings();
+        assert_eq!(standings.0[0].name, "bob");
+        assert_eq!(standings.0[0].score, 14);
+        assert_eq!(standings.0[1].score, 11);
+    }
+}
@@ -0,0 +1,430 @@
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use log::{info, warn};
+
+use super::session::{GameSession, Guess, RatingThresholds};
+use super::timer::Timer;
+use super::NineP;
+use crate::word_tree::WordTree;
+
+/// First line of every save file, bumped whenever the format changes.
+const HEADER: &str = "nine_q save 1";
+
+/// Everything needed to pick a [`GameSession`] back up later.
+///
+/// Saved as a small line based text file:
+///
+/// ```text
+/// nine_q save 1
+/// board aspersion
+/// centre n
+/// ratings 33 50 75
+/// dictionary 84c2a3e4b1d0f5a7
+/// started 1760745600
+/// saved 1760749200
+/// timer 300000 41250
+/// found person
+/// found prison
+/// revealed spire 2
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SavedGame {
+    pub board: String,
+    pub centre: Option<char>,
+    pub thresholds: RatingThresholds,
+    /// [`WordTree::fingerprint`] of the word list the game was played with.
+    pub dictionary: u64,
+    pub started: SystemTime,
+    pub saved: SystemTime,
+    /// Time limit and time used so far, for timed games.
+    pub timer: Option<(Duration, Duration)>,
+    pub found: Vec<String>,
+    /// Words with letters revealed by hints, and how many letters.
+    pub revealed: Vec<(String, usize)>,
+}
+
+/// A session restored from a [`SavedGame`].
+pub struct Resumed {
+    pub session: GameSession,
+    /// The word list is not the one the game was saved with.
+    pub dictionary_changed: bool,
+    /// Previously found words that are no longer accepted by the current word list.
+    pub dropped_words: Vec<String>,
+}
+
+impl SavedGame {
+    pub fn from_session(session: &GameSession) -> Self {
+        let puzzle = session.puzzle();
+        SavedGame {
+            board: puzzle.letters().to_string(),
+            centre: puzzle.centre(),
+            thresholds: session.thresholds(),
+            dictionary: puzzle.word_tree().fingerprint(),
+            started: session.started(),
+            saved: SystemTime::now(),
+            timer: session.timer().map(|t| (t.limit(), t.elapsed())),
+            found: session.found_words().to_vec(),
+            revealed: session.revealed(),
+        }
+    }
+
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "{}", HEADER)?;
+        writeln!(writer, "board {}", self.board)?;
+        if let Some(centre) = self.centre {
+            writeln!(writer, "centre {}", centre)?;
+        }
+        writeln!(
+            writer,
+            "ratings {} {} {}",
+            self.thresholds.good, self.thresholds.very_good, self.thresholds.excellent
+        )?;
+        writeln!(writer, "dictionary {:016x}", self.dictionary)?;
+        writeln!(writer, "started {}", unix_seconds(self.started))?;
+        writeln!(writer, "saved {}", unix_seconds(self.saved))?;
+        if let Some((limit, used)) = self.timer {
+            writeln!(writer, "timer {} {}", limit.as_millis(), used.as_millis())?;
+        }
+        for word in &self.found {
+            writeln!(writer, "found {}", word)?;
+        }
+        for (word, letters) in &self.revealed {
+            writeln!(writer, "revealed {} {}", word, letters)?;
+        }
+        writer.flush()
+    }
+
+    pub fn read<R: BufRead>(reader: R) -> io::Result<Self> {
+        let mut lines = reader.lines();
+        if lines.next().transpose()?.as_deref() != Some(HEADER) {
+            return Err(invalid("not a nine_q save file"));
+        }
+
+        let mut board = None;
+        let mut centre = None;
+        let mut thresholds = RatingThresholds::default();
+        let mut dictionary = None;
+        let mut started = None;
+        let mut saved = None;
+        let mut timer = None;
+        let mut found = Vec::new();
+        let mut revealed = Vec::new();
+
+        for line in lines {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+            match key {
+                "board" => board = Some(value.to_string()),
+                "centre" => centre = value.chars().next(),
+                "ratings" => thresholds = parse_thresholds(value)?,
+                "dictionary" => {
+                    dictionary = Some(
+                        u64::from_str_radix(value, 16)
+                            .map_err(|_| invalid("bad dictionary fingerprint"))?,
+                    )
+                }
+                "started" => started = Some(parse_time(value)?),
+                "saved" => saved = Some(parse_time(value)?),
+                "timer" => timer = Some(parse_timer(value)?),
+                "found" => found.push(value.to_string()),
+                "revealed" => revealed.push(parse_revealed(value)?),
+                _ => return Err(invalid(&format!("unknown entry '{}'", key))),
+            }
+        }
+
+        let board = board.ok_or_else(|| invalid("missing board"))?;
+        if centre.is_some_and(|c| !board.contains(c)) {
+            return Err(invalid("centre letter is not on the board"));
+        }
+        Ok(SavedGame {
+            board,
+            centre,
+            thresholds,
+            dictionary: dictionary.ok_or_else(|| invalid("missing dictionary fingerprint"))?,
+            started: started.ok_or_else(|| invalid("missing start time"))?,
+            saved: saved.ok_or_else(|| invalid("missing save time"))?,
+            timer,
+            found,
+            revealed,
+        })
+    }
+
+    /// Rebuilds the session on `word_tree`, warning if it is not the word list the game was saved
+    /// with. Timed games come back paused.
+    pub fn resume(self, word_tree: impl Into<Arc<WordTree>>) -> Resumed {
+        let word_tree = word_tree.into();
+        let dictionary_changed = word_tree.fingerprint() != self.dictionary;
+        if dictionary_changed {
+            warn!("The word list has changed since this game was saved, some words may differ");
+        }
+
+        let board = NineP::new(self.board, word_tree);
+        let puzzle = match self.centre.map(|centre| board.clone().with_centre(centre)) {
+            Some(Ok(puzzle)) => puzzle,
+            Some(Err(e)) => {
+                warn!("Ignoring the saved centre, {}", e);
+                board
+            }
+            None => board,
+        };
+        let mut session = GameSession::new(puzzle)
+            .with_thresholds(self.thresholds)
+            .with_started(self.started)
+            .with_revealed(self.revealed);
+
+        let mut dropped_words = Vec::new();
+        for word in self.found {
+            if !matches!(session.guess(&word), Guess::Accepted { .. }) {
+                dropped_words.push(word);
+            }
+        }
+        // Only start the clock once the found words have been replayed
+        if let Some((limit, used)) = self.timer {
+            session = session.with_timer(Timer::paused_at(limit, used));
+        }
+        if !dropped_words.is_empty() {
+            warn!(
+                "{} found words are no longer valid: {}",
+                dropped_words.len(),
+                dropped_words.join(", ")
+            );
+        }
+
+        Resumed {
+            session,
+            dictionary_changed,
+            dropped_words,
+        }
+    }
+}
+
+impl GameSession {
+    /// Writes the save next to `path` and moves it into place, so a crash part way through
+    /// never leaves an earlier save cut short.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let path = path.as_ref();
+        let mut temp_path = path.as_os_str().to_owned();
+        temp_path.push(format!(".{}.tmp", std::process::id()));
+        let result = (|| {
+            let file = File::create(&temp_path)?;
+            SavedGame::from_session(self).write(BufWriter::new(&file))?;
+            file.sync_all()?;
+            fs::rename(&temp_path, path)
+        })();
+        if result.is_err() {
+            let _ = fs::remove_file(&temp_path);
+        }
+        result?;
+        info!("Saved game to {}", path.display());
+        Ok(())
+    }
+
+    pub fn load<P: AsRef<Path>>(
+        path: P,
+        word_tree: impl Into<Arc<WordTree>>,
+    ) -> io::Result<Resumed> {
+        let saved = SavedGame::read(BufReader::new(File::open(&path)?))?;
+        info!("Loaded game from {}", path.as_ref().display());
+        Ok(saved.resume(word_tree))
+    }
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("Invalid save file: {}", message),
+    )
+}
+
+fn unix_seconds(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+fn parse_time(value: &str) -> io::Result<SystemTime> {
+    let seconds = value.parse::<u64>().map_err(|_| invalid("bad timestamp"))?;
+    Ok(UNIX_EPOCH + Duration::from_secs(seconds))
+}
+
+fn parse_timer(value: &str) -> io::Result<(Duration, Duration)> {
+    let values = value
+        .split_whitespace()
+        .map(|v| v.parse::<u64>().map(Duration::from_millis))
+        .collect::<Result<Vec<Duration>, _>>()
+        .map_err(|_| invalid("bad timer"))?;
+    match values[..] {
+        [limit, used] => Ok((limit, used)),
+        _ => Err(invalid("bad timer")),
+    }
+}
+
+fn parse_revealed(value: &str) -> io::Result<(String, usize)> {
+    match value.split_whitespace().collect::<Vec<&str>>()[..] {
+        [word, letters] => Ok((
+            word.to_string(),
+            letters
+                .parse()
+                .map_err(|_| invalid("bad revealed letters"))?,
+        )),
+        _ => Err(invalid("bad revealed letters")),
+    }
+}
+
+fn parse_thresholds(value: &str) -> io::Result<RatingThresholds> {
+    let values = value
+        .split_whitespace()
+        .map(|v| v.parse::<f32>())
+        .collect::<Result<Vec<f32>, _>>()
+        .map_err(|_| invalid("bad ratings"))?;
+    match values[..] {
+        [good, very_good, excellent] => Ok(RatingThresholds {
+            good,
+            very_good,
+            excellent,
+        }),
+        _ => Err(invalid("bad ratings")),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::init_logger;
+
+    use super::*;
+
+    fn words(extra: &[&str]) -> WordTree {
+        let mut words = ["person", "prison", "prions", "rosin", "spire"]
+            .map(String::from)
+            .to_vec();
+        words.extend(extra.iter().map(|w| w.to_string()));
+        WordTree::new(&words)
+    }
+
+    #[test]
+    fn round_trip() {
+        init_logger();
+        let puzzle = NineP::new("aspersion".to_string(), words(&[]))
+            .with_centre('n')
+            .unwrap();
+        let mut session = GameSession::new(puzzle);
+        session.guess("rosin");
+        session.guess("prison");
+
+        let mut file = Vec::new();
+        let saved = SavedGame::from_session(&session);
+        saved.write(&mut file).unwrap();
+        let read = SavedGame::read(file.as_slice()).unwrap();
+        assert_eq!(read.board, "aspersion");
+        assert_eq!(read.centre, Some('n'));
+        assert_eq!(read.found, ["rosin", "prison"]);
+        assert_eq!(unix_seconds(read.started), unix_seconds(session.started()));
+
+        let resumed = read.resume(words(&[]));
+        assert!(!resumed.dictionary_changed);
+        assert!(resumed.dropped_words.is_empty());
+        assert_eq!(resumed.session.found_words(), session.found_words());
+        assert_eq!(resumed.session.score(), session.score());
+        assert_eq!(resumed.session.total_words(), session.total_words());
+        assert!(resumed.session.timer().is_none());
+    }
+
+    #[test]
+    fn revealed_hints() {
+        init_logger();
+        let puzzle = NineP::new("aspersion".to_string(), words(&[]));
+        let mut session = GameSession::new(puzzle);
+        session.reveal_next_letter(0).unwrap();
+        session.reveal_next_letter(0).unwrap();
+        let revealed = session.revealed();
+        assert_eq!(revealed.len(), 1);
+        assert_eq!(revealed[0].1, 2);
+
+        let path = std::env::temp_dir().join(format!("nine_q_save_{}.txt", std::process::id()));
+        session.save(&path).unwrap();
+        // Saving again replaces the file rather than adding to it
+        session.save(&path).unwrap();
+        let resumed = GameSession::load(&path, words(&[])).unwrap().session;
+        fs::remove_file(&path).unwrap();
+        assert_eq!(resumed.revealed(), revealed);
+        let mut temp_path = path.clone().into_os_string();
+        temp_path.push(format!(".{}.tmp", std::process::id()));
+        assert!(!Path::new(&temp_path).exists());
+
+        let error = session
+            .save(
+                std::env::temp_dir()
+                    .join("nine_q_missing_dir")
+                    .join("save.txt"),
+            )
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn timed_game() {
+        init_logger();
+        let puzzle = NineP::new("aspersion".to_string(), words(&[]));
+        let mut session = GameSession::new(puzzle).with_time_limit(Duration::from_secs(300));
+        session.guess("rosin");
+        session.pause();
+
+        let mut file = Vec::new();
+        SavedGame::from_session(&session).write(&mut file).unwrap();
+        let read = SavedGame::read(file.as_slice()).unwrap();
+        let (limit, used) = read.timer.unwrap();
+        assert_eq!(limit, Duration::from_secs(300));
+        assert!(used < Duration::from_secs(1));
+
+        let mut resumed = read.resume(words(&[])).session;
+        assert_eq!(resumed.found_words(), ["rosin"]);
+        assert!(resumed.timer().unwrap().is_paused());
+        assert_eq!(resumed.guess("prison"), Guess::Paused);
+        resumed.resume();
+        assert_eq!(resumed.guess("prison"), Guess::Accepted { points: 6 });
+    }
+
+    #[test]
+    fn changed_dictionary() {
+        init_logger();
+        let puzzle = NineP::new("aspersion".to_string(), words(&["aspersion"]));
+        let mut session = GameSession::new(puzzle);
+        session.guess("aspersion");
+        session.guess("spire");
+
+        let mut file = Vec::new();
+        SavedGame::from_session(&session).write(&mut file).unwrap();
+
+        let resumed = SavedGame::read(file.as_slice()).unwrap().resume(words(&[]));
+        assert!(resumed.dictionary_changed);
+        assert_eq!(resumed.dropped_words, ["aspersion"]);
+        assert_eq!(resumed.session.found_words(), ["spire"]);
+    }
+
+    #[test]
+    fn bad_files() {
+        init_logger();
+        assert!(SavedGame::read("hello".as_bytes()).is_err());
+        assert!(SavedGame::read(format!("{}\nboard abc\n", HEADER).as_bytes()).is_err());
+        assert!(SavedGame::read(
+            format!(
+                "{}\nboard abc\ndictionary 1\nstarted 0\nsaved 0\nscore 9\n",
+                HEADER
+            )
+            .as_bytes()
+        )
+        .is_err());
+        assert!(SavedGame::read(
+            format!("{}\nboard abc\ndictionary 1\nstarted 0\nsaved 0\n", HEADER).as_bytes()
+        )
+        .is_ok());
+    }
+}
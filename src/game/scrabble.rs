@@ -0,0 +1,765 @@
+use std::fmt;
+use std::sync::Arc;
+
+use log::debug;
+
+use crate::word_tree::{LetterNode, WordTree};
+
+/// Width (and height) of the board.
+pub const SIZE: usize = 15;
+/// Most tiles a rack can hold.
+pub const RACK_SIZE: usize = 7;
+/// Extra points for using every tile on the rack in one move.
+pub const BINGO_BONUS: u32 = 50;
+
+const CENTRE: (usize, usize) = (SIZE / 2, SIZE / 2);
+
+/// Points for each letter, `a` to `z`.
+const LETTER_VALUES: [u32; 26] = [
+    1, 3, 3, 2, 1, 4, 2, 4, 1, 8, 5, 1, 3, 1, 1, 3, 10, 1, 1, 1, 1, 4, 4, 8, 4, 10,
+];
+
+/// Premium squares in the top-left quarter of the board, as `(row, column)` with `row <= column`.
+/// The rest of the board is a mirror image of these.
+const TRIPLE_WORD: &[(usize, usize)] = &[(0, 0), (0, 7)];
+const DOUBLE_WORD: &[(usize, usize)] = &[(1, 1), (2, 2), (3, 3), (4, 4), (7, 7)];
+const TRIPLE_LETTER: &[(usize, usize)] = &[(1, 5), (5, 5)];
+const DOUBLE_LETTER: &[(usize, usize)] = &[(0, 3), (2, 6), (3, 7), (6, 6)];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Square {
+    Normal,
+    DoubleLetter,
+    TripleLetter,
+    DoubleWord,
+    TripleWord,
+}
+
+impl Square {
+    /// The square at `(row, column)` on the standard board.
+    pub fn at(row: usize, column: usize) -> Self {
+        let fold = |i: usize| i.min(SIZE - 1 - i);
+        let (a, b) = (fold(row), fold(column));
+        let key = (a.min(b), a.max(b));
+
+        if TRIPLE_WORD.contains(&key) {
+            Square::TripleWord
+        } else if DOUBLE_WORD.contains(&key) {
+            Square::DoubleWord
+        } else if TRIPLE_LETTER.contains(&key) {
+            Square::TripleLetter
+        } else if DOUBLE_LETTER.contains(&key) {
+            Square::DoubleLetter
+        } else {
+            Square::Normal
+        }
+    }
+
+    fn letter_multiplier(self) -> u32 {
+        match self {
+            Square::DoubleLetter => 2,
+            Square::TripleLetter => 3,
+            _ => 1,
+        }
+    }
+
+    fn word_multiplier(self) -> u32 {
+        match self {
+            Square::DoubleWord => 2,
+            Square::TripleWord => 3,
+            _ => 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Across,
+    Down,
+}
+
+impl Direction {
+    /// The `(row, column)` of the square `position` along `line`.
+    fn square(self, line: usize, position: usize) -> (usize, usize) {
+        match self {
+            Direction::Across => (line, position),
+            Direction::Down => (position, line),
+        }
+    }
+
+    fn other(self) -> Self {
+        match self {
+            Direction::Across => Direction::Down,
+            Direction::Down => Direction::Across,
+        }
+    }
+}
+
+/// A tile on the board. Blanks stand in for a letter but score nothing.
+///
+/// Tiles always hold one of the letters `a` to `z`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tile {
+    letter: char,
+    blank: bool,
+}
+
+impl Tile {
+    /// The tile for `letter`, in either case, or `None` if it is not a letter from `a` to `z`.
+    pub fn letter(letter: char) -> Option<Self> {
+        letter
+            .is_ascii_alphabetic()
+            .then(|| Tile::new(letter.to_ascii_lowercase(), false))
+    }
+
+    /// A blank standing in for `letter`, see [`Self::letter`].
+    pub fn blank(letter: char) -> Option<Self> {
+        letter
+            .is_ascii_alphabetic()
+            .then(|| Tile::new(letter.to_ascii_lowercase(), true))
+    }
+
+    /// For letters already known to be `a` to `z`.
+    fn new(letter: char, blank: bool) -> Self {
+        Tile { letter, blank }
+    }
+
+    /// The letter on the tile, or that a blank stands in for, in lower case.
+    pub fn as_char(&self) -> char {
+        self.letter
+    }
+
+    pub fn is_blank(&self) -> bool {
+        self.blank
+    }
+
+    pub fn value(&self) -> u32 {
+        match self.blank {
+            true => 0,
+            false => LETTER_VALUES[(self.letter as u8 - b'a') as usize],
+        }
+    }
+}
+
+/// The tiles a player can place, `?` standing for a blank.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rack {
+    letters: [u8; 26],
+    blanks: u8,
+}
+
+/// Reasons tiles can not be made into a [`Rack`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidRack {
+    /// A rack holds at most [`RACK_SIZE`] tiles, this many were given.
+    TooManyTiles(usize),
+    /// Tiles are the letters `a` to `z` or `?`.
+    Letter(char),
+}
+
+impl fmt::Display for InvalidRack {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidRack::TooManyTiles(n) => {
+                write!(f, "a rack holds at most {} tiles, got {}", RACK_SIZE, n)
+            }
+            InvalidRack::Letter(c) => write!(f, "'{}' is not a tile", c),
+        }
+    }
+}
+
+impl std::error::Error for InvalidRack {}
+
+impl Rack {
+    pub fn new(tiles: &str) -> Result<Self, InvalidRack> {
+        let count = tiles.chars().count();
+        if count > RACK_SIZE {
+            return Err(InvalidRack::TooManyTiles(count));
+        }
+
+        let mut rack = Rack {
+            letters: [0; 26],
+            blanks: 0,
+        };
+        for c in tiles.chars() {
+            match c {
+                '?' => rack.blanks += 1,
+                'a'..='z' => rack.letters[(c as u8 - b'a') as usize] += 1,
+                _ => return Err(InvalidRack::Letter(c)),
+            }
+        }
+        Ok(rack)
+    }
+
+    pub fn len(&self) -> usize {
+        self.letters.iter().map(|n| *n as usize).sum::<usize>() + self.blanks as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// A tile put down by a [`Move`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Placement {
+    pub row: usize,
+    pub column: usize,
+    pub tile: Tile,
+}
+
+/// A legal play: the main word it makes, the tiles it puts down and what it scores, including
+/// any words formed across it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Move {
+    /// Square of the first letter of `word`.
+    pub row: usize,
+    pub column: usize,
+    pub direction: Direction,
+    pub word: String,
+    pub placed: Vec<Placement>,
+    pub score: u32,
+}
+
+/// Reasons a word can not be put on a [`Board`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidPlacement {
+    /// The word runs off the edge of the board.
+    OffBoard,
+    /// A different tile is already on this `(row, column)`.
+    Occupied(usize, usize),
+    /// Tiles are the letters `a` to `z`, or `A` to `Z` for a blank.
+    Letter(char),
+}
+
+impl fmt::Display for InvalidPlacement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidPlacement::OffBoard => write!(f, "the word runs off the board"),
+            InvalidPlacement::Occupied(row, column) => {
+                write!(f, "square ({}, {}) already holds another tile", row, column)
+            }
+            InvalidPlacement::Letter(c) => write!(f, "'{}' is not a tile", c),
+        }
+    }
+}
+
+impl std::error::Error for InvalidPlacement {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Board {
+    cells: Vec<Option<Tile>>,
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Board {
+            cells: vec![None; SIZE * SIZE],
+        }
+    }
+}
+
+impl Board {
+    pub fn get(&self, row: usize, column: usize) -> Option<Tile> {
+        self.cells[row * SIZE + column]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.iter().all(Option::is_none)
+    }
+
+    /// Writes `word` onto the board without checking it against a word list, e.g. to set up a
+    /// game in progress. Upper case letters are placed as blanks.
+    pub fn place(
+        &mut self,
+        row: usize,
+        column: usize,
+        direction: Direction,
+        word: &str,
+    ) -> Result<(), InvalidPlacement> {
+        let (line, start) = match direction {
+            Direction::Across => (row, column),
+            Direction::Down => (column, row),
+        };
+        if line >= SIZE || start + word.chars().count() > SIZE {
+            return Err(InvalidPlacement::OffBoard);
+        }
+        if let Some(c) = word.chars().find(|c| !c.is_ascii_alphabetic()) {
+            return Err(InvalidPlacement::Letter(c));
+        }
+
+        let tiles = word
+            .chars()
+            .enumerate()
+            .map(|(i, c)| {
+                let (row, column) = direction.square(line, start + i);
+                let tile = match c.is_ascii_uppercase() {
+                    true => Tile::new(c.to_ascii_lowercase(), true),
+                    false => Tile::new(c, false),
+                };
+                match self.get(row, column) {
+                    Some(existing) if existing != tile => {
+                        Err(InvalidPlacement::Occupied(row, column))
+                    }
+                    _ => Ok(Placement { row, column, tile }),
+                }
+            })
+            .collect::<Result<Vec<Placement>, InvalidPlacement>>()?;
+
+        for placement in tiles {
+            self.cells[placement.row * SIZE + placement.column] = Some(placement.tile);
+        }
+        Ok(())
+    }
+
+    /// Puts down the tiles from a move found by [`MoveGenerator::moves`].
+    pub fn play(&mut self, play: &Move) {
+        for placement in &play.placed {
+            self.cells[placement.row * SIZE + placement.column] = Some(placement.tile);
+        }
+    }
+
+    /// Whether a tile at `(row, column)` would touch one already on the board.
+    fn is_anchor(&self, row: usize, column: usize) -> bool {
+        if self.get(row, column).is_some() {
+            return false;
+        }
+        if self.is_empty() {
+            return (row, column) == CENTRE;
+        }
+        [(-1, 0), (1, 0), (0, -1), (0, 1)].iter().any(|(dr, dc)| {
+            let (r, c) = (row as isize + dr, column as isize + dc);
+            (0..SIZE as isize).contains(&r)
+                && (0..SIZE as isize).contains(&c)
+                && self.get(r as usize, c as usize).is_some()
+        })
+    }
+
+    /// The tiles running from `(row, column)` in `direction` (or against it when `forwards` is
+    /// false), not including the square itself, in board order.
+    fn run(&self, row: usize, column: usize, direction: Direction, forwards: bool) -> Vec<Tile> {
+        let (line, position) = match direction {
+            Direction::Across => (row, column),
+            Direction::Down => (column, row),
+        };
+        let positions: Box<dyn Iterator<Item = usize>> = match forwards {
+            true => Box::new(position + 1..SIZE),
+            false => Box::new((0..position).rev()),
+        };
+        let mut tiles = positions
+            .map(|p| direction.square(line, p))
+            .map_while(|(r, c)| self.get(r, c))
+            .collect::<Vec<Tile>>();
+        if !forwards {
+            tiles.reverse();
+        }
+        tiles
+    }
+}
+
+/// One line per row, `.` for empty squares and blanks in lower case.
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, row) in self.cells.chunks(SIZE).enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            let row = row
+                .iter()
+                .map(|cell| match cell {
+                    Some(tile) if tile.blank => tile.letter.to_string(),
+                    Some(tile) => tile.letter.to_ascii_uppercase().to_string(),
+                    None => ".".to_string(),
+                })
+                .collect::<Vec<String>>();
+            write!(f, "{}", row.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Which letters may go on an empty square given the tiles either side of it in the other
+/// direction.
+#[derive(Clone, Copy)]
+struct CrossCheck {
+    /// Bit `n` is set if letter `n` is allowed.
+    allowed: u32,
+    /// Value of the tiles the square would join onto, `None` if it forms no word across the move.
+    score: Option<u32>,
+}
+
+/// One row or column being searched for moves.
+struct Line {
+    direction: Direction,
+    index: usize,
+    cells: Vec<Option<Tile>>,
+    anchors: Vec<bool>,
+    cross_checks: Vec<CrossCheck>,
+}
+
+/// Finds every legal move on a board, using the word tree both to build words along a line and to
+/// check the words they form across it.
+pub struct MoveGenerator {
+    word_tree: Arc<WordTree>,
+}
+
+impl MoveGenerator {
+    pub fn new(word_tree: impl Into<Arc<WordTree>>) -> Self {
+        MoveGenerator {
+            word_tree: word_tree.into(),
+        }
+    }
+
+    /// Every move `rack` can make on `board`, highest scoring first.
+    pub fn moves(&self, board: &Board, rack: &Rack) -> Vec<Move> {
+        let mut moves = Vec::new();
+        for direction in [Direction::Across, Direction::Down] {
+            for index in 0..SIZE {
+                let line = self.line(board, direction, index);
+                if !line.anchors.contains(&true) {
+                    continue;
+                }
+                for start in 0..SIZE {
+                    // Words must start after an empty square and reach an anchor
+                    if (start > 0 && line.cells[start - 1].is_some())
+                        || !line.anchors[start..].contains(&true)
+                    {
+                        continue;
+                    }
+                    let mut rack = rack.clone();
+                    self.extend(
+                        &line,
+                        start,
+                        start,
+                        self.word_tree.root(),
+                        &mut rack,
+                        &mut Vec::new(),
+                        &mut Vec::new(),
+                        false,
+                        &mut moves,
+                    );
+                }
+            }
+        }
+
+        // A single tile making words both ways is found from both directions, keep it once.
+        moves.retain(|m| {
+            let (row, column) = (m.placed[0].row, m.placed[0].column);
+            m.direction == Direction::Across
+                || m.placed.len() > 1
+                || (board.run(row, column, Direction::Across, false).is_empty()
+                    && board.run(row, column, Direction::Across, true).is_empty())
+        });
+        moves.sort_by(|a, b| b.score.cmp(&a.score).then(a.word.cmp(&b.word)));
+        debug!("Found {} moves", moves.len());
+        moves
+    }
+
+    fn line(&self, board: &Board, direction: Direction, index: usize) -> Line {
+        let squares = (0..SIZE).map(|p| direction.square(index, p));
+        Line {
+            direction,
+            index,
+            cells: squares.clone().map(|(r, c)| board.get(r, c)).collect(),
+            anchors: squares
+                .clone()
+                .map(|(r, c)| board.is_anchor(r, c))
+                .collect(),
+            cross_checks: squares
+                .map(|(r, c)| self.cross_check(board, r, c, direction.other()))
+                .collect(),
+        }
+    }
+
+    fn cross_check(
+        &self,
+        board: &Board,
+        row: usize,
+        column: usize,
+        across: Direction,
+    ) -> CrossCheck {
+        let before = board.run(row, column, across, false);
+        let after = board.run(row, column, across, true);
+        if before.is_empty() && after.is_empty() {
+            return CrossCheck {
+                allowed: u32::MAX,
+                score: None,
+            };
+        }
+
+        let prefix = before.iter().map(|t| t.letter).collect::<String>();
+        let suffix = after.iter().map(|t| t.letter).collect::<String>();
+        let allowed = (b'a'..=b'z')
+            .enumerate()
+            .filter(|(_, c)| {
+                self.word_tree
+                    .contains(&format!("{}{}{}", prefix, *c as char, suffix))
+            })
+            .fold(0, |mask, (i, _)| mask | (1 << i));
+
+        CrossCheck {
+            allowed,
+            score: Some(before.iter().chain(&after).map(Tile::value).sum()),
+        }
+    }
+
+    /// Builds words along `line` from `start`, a square at a time, following `node` down the tree
+    /// through tiles already on the board and trying tiles from the rack on empty squares.
+    #[allow(clippy::too_many_arguments)]
+    fn extend(
+        &self,
+        line: &Line,
+        start: usize,
+        position: usize,
+        node: &LetterNode,
+        rack: &mut Rack,
+        word: &mut Vec<Tile>,
+        placed: &mut Vec<usize>,
+        anchored: bool,
+        moves: &mut Vec<Move>,
+    ) {
+        let ends_here = position == SIZE || line.cells[position].is_none();
+        if ends_here && anchored && word.len() >= 2 && node.is_terminator() {
+            moves.push(self.build_move(line, start, word, placed));
+        }
+        if position == SIZE {
+            return;
+        }
+
+        if let Some(tile) = line.cells[position] {
+            if let Some(child) = node.child(tile.letter) {
+                word.push(tile);
+                self.extend(
+                    line,
+                    start,
+                    position + 1,
+                    child,
+                    rack,
+                    word,
+                    placed,
+                    anchored,
+                    moves,
+                );
+                word.pop();
+            }
+            return;
+        }
+
+        let anchored = anchored || line.anchors[position];
+        let allowed = line.cross_checks[position].allowed;
+        for (i, letter) in ('a'..='z').enumerate() {
+            if allowed & (1 << i) == 0 {
+                continue;
+            }
+            let Some(child) = node.child(letter) else {
+                continue;
+            };
+
+            let mut tiles = Vec::with_capacity(2);
+            if rack.letters[i] > 0 {
+                tiles.push(Tile::new(letter, false));
+            }
+            if rack.blanks > 0 {
+                tiles.push(Tile::new(letter, true));
+            }
+            for tile in tiles {
+                match tile.blank {
+                    true => rack.blanks -= 1,
+                    false => rack.letters[i] -= 1,
+                }
+                word.push(tile);
+                placed.push(position);
+
+                self.extend(
+                    line,
+                    start,
+                    position + 1,
+                    child,
+                    rack,
+                    word,
+                    placed,
+                    anchored,
+                    moves,
+                );
+
+                placed.pop();
+                word.pop();
+                match tile.blank {
+                    true => rack.blanks += 1,
+                    false => rack.letters[i] += 1,
+                }
+            }
+        }
+    }
+
+    fn build_move(&self, line: &Line, start: usize, word: &[Tile], placed: &[usize]) -> Move {
+        let mut main = 0;
+        let mut multiplier = 1;
+        let mut cross_words = 0;
+        for (i, tile) in word.iter().enumerate() {
+            let position = start + i;
+            if !placed.contains(&position) {
+                main += tile.value();
+                continue;
+            }
+
+            let (row, column) = line.direction.square(line.index, position);
+            let square = Square::at(row, column);
+            let value = tile.value() * square.letter_multiplier();
+            main += value;
+            multiplier *= square.word_multiplier();
+            if let Some(score) = line.cross_checks[position].score {
+                cross_words += (score + value) * square.word_multiplier();
+            }
+        }
+        let bingo = match placed.len() {
+            RACK_SIZE => BINGO_BONUS,
+            _ => 0,
+        };
+
+        let (row, column) = line.direction.square(line.index, start);
+        Move {
+            row,
+            column,
+            direction: line.direction,
+            word: word.iter().map(|t| t.letter).collect(),
+            placed: placed
+                .iter()
+                .map(|p| {
+                    let (row, column) = line.direction.square(line.index, *p);
+                    Placement {
+                        row,
+                        column,
+                        tile: word[p - start],
+                    }
+                })
+                .collect(),
+            score: main * multiplier + cross_words + bingo,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::init_logger;
+
+    use super::*;
+
+    fn generator(words: &[&str]) -> MoveGenerator {
+        let words = words.iter().map(|w| w.to_string()).collect::<Vec<String>>();
+        MoveGenerator::new(WordTree::new(&words))
+    }
+
+    fn summary(moves: &[Move]) -> Vec<(&str, u32)> {
+        moves.iter().map(|m| (m.word.as_str(), m.score)).collect()
+    }
+
+    #[test]
+    fn premium_squares() {
+        init_logger();
+        let squares = (0..SIZE)
+            .flat_map(|r| (0..SIZE).map(move |c| Square::at(r, c)))
+            .collect::<Vec<Square>>();
+        let count = |square| squares.iter().filter(|s| **s == square).count();
+        assert_eq!(count(Square::TripleWord), 8);
+        assert_eq!(count(Square::DoubleWord), 17);
+        assert_eq!(count(Square::TripleLetter), 12);
+        assert_eq!(count(Square::DoubleLetter), 24);
+        assert_eq!(Square::at(7, 7), Square::DoubleWord);
+        assert_eq!(Square::at(14, 11), Square::DoubleLetter);
+    }
+
+    #[test]
+    fn first_move() {
+        init_logger();
+        let generator = generator(&["cat", "act", "at"]);
+        let moves = generator.moves(&Board::default(), &Rack::new("cat").unwrap());
+
+        assert_eq!(moves.len(), 16);
+        assert_eq!(moves[0].score, 10);
+        for m in &moves {
+            assert!(m.placed.iter().any(|p| (p.row, p.column) == CENTRE));
+        }
+    }
+
+    #[test]
+    fn hooks() {
+        init_logger();
+        let generator = generator(&["cat", "cats", "at", "as", "scat"]);
+        let mut board = Board::default();
+        board.place(7, 6, Direction::Across, "cat").unwrap();
+
+        let moves = generator.moves(&board, &Rack::new("s").unwrap());
+        assert_eq!(summary(&moves), [("cats", 6), ("scat", 6), ("as", 2)]);
+
+        board.play(&moves[0]);
+        assert_eq!(board.get(7, 9), Tile::letter('s'));
+    }
+
+    #[test]
+    fn blanks() {
+        init_logger();
+        let generator = generator(&["cat", "cats", "at", "as", "scat"]);
+        let mut board = Board::default();
+        board.place(7, 6, Direction::Across, "cat").unwrap();
+
+        let moves = generator.moves(&board, &Rack::new("?").unwrap());
+        assert_eq!(
+            summary(&moves),
+            [("cats", 5), ("scat", 5), ("as", 1), ("at", 1), ("at", 1)]
+        );
+        assert!(moves.iter().all(|m| m.placed[0].tile.blank));
+    }
+
+    #[test]
+    fn cross_words() {
+        init_logger();
+        let generator = generator(&["cat", "cats", "so"]);
+        let mut board = Board::default();
+        board.place(7, 6, Direction::Across, "cat").unwrap();
+
+        let moves = generator.moves(&board, &Rack::new("so").unwrap());
+        let so = moves
+            .iter()
+            .find(|m| m.word == "so" && m.direction == Direction::Down)
+            .unwrap();
+        assert_eq!((so.row, so.column), (7, 9));
+        // SO, plus CATS across
+        assert_eq!(so.score, 2 + 6);
+    }
+
+    #[test]
+    fn invalid_input() {
+        init_logger();
+        assert_eq!(Rack::new("abcdefgh"), Err(InvalidRack::TooManyTiles(8)));
+        assert_eq!(Rack::new("ab1"), Err(InvalidRack::Letter('1')));
+        assert_eq!(Rack::new("ab?").unwrap().len(), 3);
+
+        let mut board = Board::default();
+        assert_eq!(
+            board.place(0, 12, Direction::Across, "cats"),
+            Err(InvalidPlacement::OffBoard)
+        );
+        board.place(7, 6, Direction::Across, "cat").unwrap();
+        assert_eq!(board.place(5, 7, Direction::Down, "boat"), Ok(()));
+        assert_eq!(
+            board.place(6, 8, Direction::Down, "oat"),
+            Err(InvalidPlacement::Occupied(7, 8))
+        );
+        assert_eq!(
+            board.place(0, 0, Direction::Across, "c?t"),
+            Err(InvalidPlacement::Letter('?'))
+        );
+        assert_eq!(
+            board.place(0, 0, Direction::Across, "café"),
+            Err(InvalidPlacement::Letter('é'))
+        );
+        assert_eq!(board.get(0, 0), None);
+
+        assert_eq!(Tile::letter('A'), Tile::letter('a'));
+        assert_eq!(Tile::letter('z').unwrap().value(), 10);
+        assert_eq!(Tile::blank('Q').unwrap().value(), 0);
+        assert_eq!(Tile::letter('1'), None);
+        assert_eq!(Tile::blank('é'), None);
+    }
+}
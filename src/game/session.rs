@@ -0,0 +1,514 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::time::{Duration, SystemTime};
+
+use log::debug;
+
+use super::{hints::Hints, timer::Timer, InvalidGuess, NineP};
+
+/// How well a player is doing, based on the share of words they have found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Rating {
+    Beginner,
+    Good,
+    VeryGood,
+    Excellent,
+}
+
+impl fmt::Display for Rating {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rating::Beginner => write!(f, "Beginner"),
+            Rating::Good => write!(f, "Good"),
+            Rating::VeryGood => write!(f, "Very Good"),
+            Rating::Excellent => write!(f, "Excellent"),
+        }
+    }
+}
+
+/// Percentage of words that must be found to reach each [`Rating`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RatingThresholds {
+    pub good: f32,
+    pub very_good: f32,
+    pub excellent: f32,
+}
+
+impl Default for RatingThresholds {
+    fn default() -> Self {
+        RatingThresholds {
+            good: 33.0,
+            very_good: 50.0,
+            excellent: 75.0,
+        }
+    }
+}
+
+impl RatingThresholds {
+    pub fn rating(&self, progress: f32) -> Rating {
+        if progress >= self.excellent {
+            Rating::Excellent
+        } else if progress >= self.very_good {
+            Rating::VeryGood
+        } else if progress >= self.good {
+            Rating::Good
+        } else {
+            Rating::Beginner
+        }
+    }
+}
+
+/// The outcome of a single guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Guess {
+    /// A new word, worth `points`.
+    Accepted {
+        points: u32,
+    },
+    AlreadyFound,
+    Invalid(InvalidGuess),
+    /// The time limit has run out.
+    TimeUp,
+    /// The timer is paused.
+    Paused,
+}
+
+/// Why a timed game is not taking guesses or giving hints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stopped {
+    /// The time limit has run out.
+    TimeUp,
+    /// The timer is paused.
+    Paused,
+}
+
+impl From<Stopped> for Guess {
+    fn from(stopped: Stopped) -> Self {
+        match stopped {
+            Stopped::TimeUp => Guess::TimeUp,
+            Stopped::Paused => Guess::Paused,
+        }
+    }
+}
+
+/// How a game went, for showing once it is over.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    pub found: usize,
+    pub total: usize,
+    pub score: u32,
+    pub max_score: u32,
+    /// Percentage (0-100) of the board's words that were found.
+    pub percentage: f32,
+    pub rating: Rating,
+    /// The highest scoring words that were not found.
+    pub best_missed: Vec<String>,
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} of {} words, {} of {} points ({:.0}%), rated {}",
+            self.found, self.total, self.score, self.max_score, self.percentage, self.rating
+        )?;
+        if !self.best_missed.is_empty() {
+            write!(f, "\nBest missed: {}", self.best_missed.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// A game in progress: a [`NineP`] board plus the words the player has found so far.
+pub struct GameSession {
+    puzzle: NineP,
+    solutions: HashSet<String>,
+    max_score: u32,
+    found: Vec<String>,
+    score: u32,
+    thresholds: RatingThresholds,
+    revealed: HashMap<String, usize>,
+    started: SystemTime,
+    timer: Option<Timer>,
+}
+
+impl GameSession {
+    pub fn new(puzzle: NineP) -> Self {
+        let solutions: HashSet<String> = puzzle.solve().into_iter().collect();
+        let max_score = solutions.iter().map(|w| puzzle.score_word(w)).sum();
+        debug!(
+            "Started session with {} solutions worth {} points",
+            solutions.len(),
+            max_score
+        );
+
+        GameSession {
+            puzzle,
+            solutions,
+            max_score,
+            found: Vec::new(),
+            score: 0,
+            thresholds: RatingThresholds::default(),
+            revealed: HashMap::new(),
+            started: SystemTime::now(),
+            timer: None,
+        }
+    }
+
+    pub fn with_thresholds(mut self, thresholds: RatingThresholds) -> Self {
+        self.thresholds = thresholds;
+        self
+    }
+
+    /// Turns this into a timed game, with the clock starting now.
+    pub fn with_time_limit(mut self, limit: Duration) -> Self {
+        self.timer = Some(Timer::new(limit));
+        self
+    }
+
+    pub(super) fn with_timer(mut self, timer: Timer) -> Self {
+        self.timer = Some(timer);
+        self
+    }
+
+    /// The countdown, for timed games.
+    pub fn timer(&self) -> Option<&Timer> {
+        self.timer.as_ref()
+    }
+
+    /// Whether a timed game has run out of time.
+    pub fn is_time_up(&self) -> bool {
+        self.timer.as_ref().is_some_and(Timer::is_expired)
+    }
+
+    /// Whether the clock is stopping play, `None` if the game is untimed or the clock is running.
+    pub fn stopped(&self) -> Option<Stopped> {
+        let timer = self.timer.as_ref()?;
+        if timer.is_expired() {
+            Some(Stopped::TimeUp)
+        } else if timer.is_paused() {
+            Some(Stopped::Paused)
+        } else {
+            None
+        }
+    }
+
+    pub fn pause(&mut self) {
+        if let Some(timer) = &mut self.timer {
+            timer.pause();
+        }
+    }
+
+    pub fn resume(&mut self) {
+        if let Some(timer) = &mut self.timer {
+            timer.resume();
+        }
+    }
+
+    /// Used when resuming a saved game, so the session keeps its original start time.
+    pub(super) fn with_started(mut self, started: SystemTime) -> Self {
+        self.started = started;
+        self
+    }
+
+    pub fn thresholds(&self) -> RatingThresholds {
+        self.thresholds
+    }
+
+    pub fn started(&self) -> SystemTime {
+        self.started
+    }
+
+    pub fn puzzle(&self) -> &NineP {
+        &self.puzzle
+    }
+
+    /// Rearranges the letters on the board, see [`NineP::shuffle`].
+    pub fn shuffle(&mut self, seed: u64) {
+        self.puzzle.shuffle(seed);
+    }
+
+    pub fn guess(&mut self, word: &str) -> Guess {
+        if let Some(stopped) = self.stopped() {
+            return stopped.into();
+        }
+
+        let word = word.trim().to_lowercase();
+        if self.found.contains(&word) {
+            return Guess::AlreadyFound;
+        }
+        if !self.solutions.contains(&word) {
+            return match self.puzzle.check_guess(&word) {
+                Err(reason) => Guess::Invalid(reason),
+                // In the word list and spellable, but not one of the board's solutions
+                Ok(()) => Guess::Invalid(InvalidGuess::NotAWord),
+            };
+        }
+
+        let points = self.puzzle.score_word(&word);
+        self.score += points;
+        self.found.push(word);
+        Guess::Accepted { points }
+    }
+
+    /// Words found so far, in the order they were found.
+    pub fn found_words(&self) -> &[String] {
+        &self.found
+    }
+
+    /// All words that have not been found yet, sorted A-Z.
+    pub fn remaining_words(&self) -> Vec<String> {
+        let mut remaining = self
+            .solutions
+            .iter()
+            .filter(|w| !self.found.contains(w))
+            .cloned()
+            .collect::<Vec<String>>();
+        remaining.sort();
+        remaining
+    }
+
+    pub fn total_words(&self) -> usize {
+        self.solutions.len()
+    }
+
+    pub fn score(&self) -> u32 {
+        self.score
+    }
+
+    pub fn max_score(&self) -> u32 {
+        self.max_score
+    }
+
+    /// Percentage (0-100) of the board's words that have been found.
+    pub fn progress(&self) -> f32 {
+        if self.solutions.is_empty() {
+            return 100.0;
+        }
+        self.found.len() as f32 * 100.0 / self.solutions.len() as f32
+    }
+
+    pub fn rating(&self) -> Rating {
+        self.thresholds.rating(self.progress())
+    }
+
+    pub fn found_full_word(&self) -> bool {
+        self.found.iter().any(|w| self.puzzle.is_full_word(w))
+    }
+
+    /// The final tally, listing up to `best_missed` of the highest scoring words that were not
+    /// found.
+    pub fn summary(&self, best_missed: usize) -> Summary {
+        let mut missed = self.remaining_words();
+        // Highest scoring first, keeping A-Z order between equal scores
+        missed.sort_by_key(|w| std::cmp::Reverse(self.puzzle.score_word(w)));
+        missed.truncate(best_missed);
+
+        Summary {
+            found: self.found.len(),
+            total: self.total_words(),
+            score: self.score,
+            max_score: self.max_score,
+            percentage: self.progress(),
+            rating: self.rating(),
+            best_missed: missed,
+        }
+    }
+
+    /// Hints about the words that have not been found yet, unless the clock is stopping play.
+    pub fn hints(&self) -> Result<Hints, Stopped> {
+        match self.stopped() {
+            Some(stopped) => Err(stopped),
+            None => Ok(Hints::new(self.remaining_words())),
+        }
+    }
+
+    /// How many letters have been revealed of each word, sorted by word.
+    pub fn revealed(&self) -> Vec<(String, usize)> {
+        let mut revealed = self
+            .revealed
+            .iter()
+            .map(|(word, letters)| (word.clone(), *letters))
+            .collect::<Vec<(String, usize)>>();
+        revealed.sort();
+        revealed
+    }
+
+    /// Carries on from letters revealed earlier, ignoring words that are not solutions.
+    pub fn with_revealed(mut self, revealed: impl IntoIterator<Item = (String, usize)>) -> Self {
+        for (word, letters) in revealed {
+            if self.solutions.contains(&word) {
+                let letters = letters.min(max_revealed(&word));
+                self.revealed.insert(word, letters);
+            }
+        }
+        self
+    }
+
+    /// Reveals one more letter of the `index`th remaining word (see [`Self::remaining_words`]),
+    /// returning every letter revealed for it so far. The last letter is never revealed.
+    ///
+    /// `Ok(None)` if there is no such word, and an error if the clock is stopping play.
+    pub fn reveal_next_letter(&mut self, index: usize) -> Result<Option<String>, Stopped> {
+        let hints = self.hints()?;
+        let Some(word) = self.remaining_words().into_iter().nth(index) else {
+            return Ok(None);
+        };
+        let max = max_revealed(&word);
+        let revealed = self.revealed.entry(word).or_insert(0);
+        *revealed = (*revealed + 1).min(max);
+        Ok(hints.reveal(index, *revealed))
+    }
+}
+
+/// How many letters of `word` can be revealed without giving it away.
+fn max_revealed(word: &str) -> usize {
+    word.chars().count().saturating_sub(1)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{init_logger, word_tree::WordTree};
+
+    use super::*;
+
+    fn session() -> GameSession {
+        let words = [
+            "tame", "team", "meat", "mate", "steam", "tames", "seat", "tease",
+        ]
+        .map(String::from);
+        GameSession::new(NineP::new("mates".to_string(), WordTree::new(&words)))
+    }
+
+    #[test]
+    fn guesses() {
+        init_logger();
+        let mut session = session();
+        assert_eq!(session.total_words(), 7);
+
+        assert_eq!(session.guess("team"), Guess::Accepted { points: 4 });
+        assert_eq!(session.guess("TEAM"), Guess::AlreadyFound);
+        assert_eq!(
+            session.guess("tease"),
+            Guess::Invalid(InvalidGuess::UnavailableLetters)
+        );
+        assert_eq!(
+            session.guess("stame"),
+            Guess::Invalid(InvalidGuess::NotAWord)
+        );
+        assert_eq!(session.found_words(), ["team".to_string()]);
+        assert_eq!(session.score(), 4);
+        assert!(!session.found_full_word());
+
+        assert_eq!(session.guess("steam"), Guess::Accepted { points: 5 });
+        assert!(session.found_full_word());
+        assert_eq!(
+            session.remaining_words(),
+            ["mate", "meat", "seat", "tame", "tames"]
+        );
+    }
+
+    #[test]
+    fn ratings() {
+        init_logger();
+        let mut session = session().with_thresholds(RatingThresholds {
+            good: 25.0,
+            very_good: 50.0,
+            excellent: 100.0,
+        });
+        assert_eq!(session.rating(), Rating::Beginner);
+
+        session.guess("team");
+        session.guess("meat");
+        assert_eq!(session.rating(), Rating::Good);
+
+        for word in ["mate", "tame"] {
+            session.guess(word);
+        }
+        assert!(session.progress() > 50.0);
+        assert_eq!(session.rating(), Rating::VeryGood);
+
+        for word in session.remaining_words() {
+            session.guess(&word);
+        }
+        assert_eq!(session.progress(), 100.0);
+        assert_eq!(session.rating(), Rating::Excellent);
+        assert_eq!(session.score(), session.max_score());
+    }
+
+    #[test]
+    fn time_limit() {
+        init_logger();
+        let mut session = session().with_time_limit(Duration::from_secs(3600));
+        assert_eq!(session.guess("team"), Guess::Accepted { points: 4 });
+
+        session.pause();
+        assert_eq!(session.guess("meat"), Guess::Paused);
+        session.resume();
+        assert_eq!(session.guess("meat"), Guess::Accepted { points: 4 });
+
+        let mut session = session.with_time_limit(Duration::ZERO);
+        assert!(session.is_time_up());
+        assert_eq!(session.guess("mate"), Guess::TimeUp);
+        assert_eq!(session.found_words(), ["team", "meat"]);
+    }
+
+    #[test]
+    fn hints_follow_the_clock() {
+        init_logger();
+        let mut session = session().with_time_limit(Duration::from_secs(3600));
+        assert!(session.hints().is_ok());
+
+        session.pause();
+        assert_eq!(session.stopped(), Some(Stopped::Paused));
+        assert_eq!(session.hints().map(|_| ()), Err(Stopped::Paused));
+        assert_eq!(session.reveal_next_letter(0), Err(Stopped::Paused));
+        assert!(session.revealed().is_empty());
+
+        let mut session = session.with_time_limit(Duration::ZERO);
+        assert_eq!(session.hints().map(|_| ()), Err(Stopped::TimeUp));
+        assert_eq!(session.reveal_next_letter(0), Err(Stopped::TimeUp));
+    }
+
+    #[test]
+    fn summary() {
+        init_logger();
+        let mut session = session();
+        session.guess("tames");
+        session.guess("team");
+
+        let summary = session.summary(3);
+        assert_eq!(summary.found, 2);
+        assert_eq!(summary.total, 7);
+        assert_eq!(summary.score, 9);
+        assert_eq!(summary.best_missed, ["steam", "mate", "meat"]);
+        assert_eq!(
+            summary.to_string(),
+            "2 of 7 words, 9 of 30 points (29%), rated Beginner\nBest missed: steam, mate, meat"
+        );
+    }
+
+    #[test]
+    fn hints() {
+        init_logger();
+        let mut session = session();
+        session.guess("mate");
+        assert_eq!(session.hints().unwrap().words_left(), 6);
+        assert_eq!(session.hints().unwrap().grid().count('m', 4), 1);
+
+        assert_eq!(session.reveal_next_letter(0), Ok(Some("m".to_string())));
+        assert_eq!(session.reveal_next_letter(0), Ok(Some("me".to_string())));
+        assert_eq!(session.reveal_next_letter(3), Ok(Some("t".to_string())));
+        assert_eq!(session.reveal_next_letter(6), Ok(None));
+
+        // The count stops short of the whole word, however often it is asked for
+        for _ in 0..5 {
+            session.reveal_next_letter(0).unwrap();
+        }
+        assert_eq!(session.reveal_next_letter(0), Ok(Some("mea".to_string())));
+        assert!(session.revealed().contains(&("meat".to_string(), 3)));
+
+        let session = session.with_revealed([("seat".to_string(), 99)]);
+        assert!(session.revealed().contains(&("seat".to_string(), 3)));
+    }
+}
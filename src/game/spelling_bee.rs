@@ -0,0 +1,273 @@
+use std::fmt;
+use std::sync::Arc;
+
+use super::{Game, InvalidGuess};
+use crate::word_tree::{LetterUse, WordTree};
+
+/// Number of letters in the honeycomb.
+pub const LETTERS: usize = 7;
+/// Shortest word that counts.
+pub const MIN_LENGTH: usize = 4;
+/// Extra points for a word using every letter.
+pub const PANGRAM_BONUS: u32 = 7;
+
+/// The seven-letter honeycomb game: letters may be used any number of times, every word must use
+/// the centre letter and be at least [`MIN_LENGTH`] letters long.
+pub struct SpellingBee {
+    letters: Vec<char>,
+    centre: char,
+    word_tree: Arc<WordTree>,
+}
+
+/// Reasons letters can not be made into a [`SpellingBee`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidHoneycomb {
+    /// The honeycomb needs [`LETTERS`] different letters, this many were given.
+    LetterCount(usize),
+    /// The centre letter is not one of the letters.
+    Centre(char),
+    /// Honeycombs may only hold the letters `a` to `z`.
+    Letter(char),
+}
+
+impl fmt::Display for InvalidHoneycomb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidHoneycomb::LetterCount(n) => {
+                write!(f, "expected {} different letters, got {}", LETTERS, n)
+            }
+            InvalidHoneycomb::Centre(c) => {
+                write!(f, "centre letter '{}' is not in the honeycomb", c)
+            }
+            InvalidHoneycomb::Letter(c) => write!(f, "'{}' can not be placed in a honeycomb", c),
+        }
+    }
+}
+
+impl std::error::Error for InvalidHoneycomb {}
+
+impl SpellingBee {
+    pub fn new(
+        letters: &str,
+        centre: char,
+        word_tree: impl Into<Arc<WordTree>>,
+    ) -> Result<Self, InvalidHoneycomb> {
+        if let Some(c) = letters.chars().find(|c| !c.is_ascii_lowercase()) {
+            return Err(InvalidHoneycomb::Letter(c));
+        }
+        let mut distinct: Vec<char> = letters.chars().collect();
+        distinct.sort_unstable();
+        distinct.dedup();
+        if distinct.len() != letters.chars().count() || distinct.len() != LETTERS {
+            return Err(InvalidHoneycomb::LetterCount(distinct.len()));
+        }
+        if !distinct.contains(&centre) {
+            return Err(InvalidHoneycomb::Centre(centre));
+        }
+
+        Ok(SpellingBee {
+            letters: letters.chars().collect(),
+            centre,
+            word_tree: word_tree.into(),
+        })
+    }
+
+    pub fn letters(&self) -> &[char] {
+        &self.letters
+    }
+
+    pub fn centre(&self) -> char {
+        self.centre
+    }
+
+    pub fn solve(&self) -> Vec<String> {
+        let letters: String = self.letters.iter().collect();
+        let mut words = self.word_tree.solve_with(&letters, LetterUse::Reuse);
+        words.retain(|w| w.chars().count() >= MIN_LENGTH && w.contains(self.centre));
+        words
+    }
+
+    /// Checks that `word` only uses honeycomb letters, is long enough, uses the centre letter and is
+    /// in the word list.
+    pub fn check_guess(&self, word: &str) -> Result<(), InvalidGuess> {
+        if !word.chars().all(|c| self.letters.contains(&c)) {
+            return Err(InvalidGuess::UnavailableLetters);
+        }
+        if word.chars().count() < MIN_LENGTH {
+            return Err(InvalidGuess::TooShort);
+        }
+        if !word.contains(self.centre) {
+            return Err(InvalidGuess::MissingCentre);
+        }
+        if self.word_tree.contains(word) {
+            Ok(())
+        } else {
+            Err(InvalidGuess::NotAWord)
+        }
+    }
+
+    /// Whether `word` uses every letter in the honeycomb.
+    pub fn is_pangram(&self, word: &str) -> bool {
+        self.letters.iter().all(|c| word.contains(*c))
+    }
+
+    pub fn pangrams(&self) -> Vec<String> {
+        self.solve()
+            .into_iter()
+            .filter(|w| self.is_pangram(w))
+            .collect()
+    }
+
+    /// Standard scoring: four-letter words score one point, longer words a point per letter, and
+    /// pangrams get a [`PANGRAM_BONUS`].
+    pub fn score_word(&self, word: &str) -> u32 {
+        let length = word.chars().count() as u32;
+        let points = if length as usize == MIN_LENGTH {
+            1
+        } else {
+            length
+        };
+        if self.is_pangram(word) {
+            points + PANGRAM_BONUS
+        } else {
+            points
+        }
+    }
+
+    pub fn max_score(&self) -> u32 {
+        self.solve().iter().map(|w| self.score_word(w)).sum()
+    }
+}
+
+impl Game for SpellingBee {
+    fn solve(&self) -> Vec<String> {
+        SpellingBee::solve(self)
+    }
+
+    fn check_guess(&self, guess: &str) -> Result<(), InvalidGuess> {
+        SpellingBee::check_guess(self, guess)
+    }
+
+    fn score(&self, word: &str) -> u32 {
+        self.score_word(word)
+    }
+
+    /// The letters in a row with the centre letter in brackets.
+    fn describe_board(&self) -> String {
+        self.letters
+            .iter()
+            .map(|c| match *c == self.centre {
+                true => format!("[{}]", c.to_ascii_uppercase()),
+                false => c.to_ascii_uppercase().to_string(),
+            })
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::init_logger;
+
+    use super::*;
+
+    fn bee() -> SpellingBee {
+        let words = [
+            "acorn",
+            "corn",
+            "crab",
+            "born",
+            "carbon",
+            "carbonara",
+            "baron",
+            "cab",
+            "acorns",
+            "robin",
+        ]
+        .map(String::from);
+        SpellingBee::new("abcnorz", 'r', WordTree::new(&words)).unwrap()
+    }
+
+    #[test]
+    fn solve() {
+        init_logger();
+        let mut words = bee().solve();
+        words.sort();
+        assert_eq!(
+            words,
+            [
+                "acorn",
+                "baron",
+                "born",
+                "carbon",
+                "carbonara",
+                "corn",
+                "crab"
+            ]
+        );
+    }
+
+    #[test]
+    fn scoring() {
+        init_logger();
+        let bee = bee();
+        assert_eq!(bee.score_word("corn"), 1);
+        assert_eq!(bee.score_word("carbon"), 6);
+        assert!(!bee.is_pangram("carbonara"));
+        assert!(bee.is_pangram("zabcornz"));
+        assert_eq!(bee.score_word("zabcornz"), 8 + PANGRAM_BONUS);
+        assert_eq!(bee.max_score(), 5 + 5 + 1 + 6 + 9 + 1 + 1);
+    }
+
+    #[test]
+    fn guesses() {
+        init_logger();
+        let bee = bee();
+        assert_eq!(bee.check_guess("carbon"), Ok(()));
+        assert_eq!(
+            bee.check_guess("acorns"),
+            Err(InvalidGuess::UnavailableLetters)
+        );
+        assert_eq!(bee.check_guess("cab"), Err(InvalidGuess::TooShort));
+        assert_eq!(bee.check_guess("zoca"), Err(InvalidGuess::MissingCentre));
+        assert_eq!(bee.check_guess("carb"), Err(InvalidGuess::NotAWord));
+        assert_eq!(bee.describe_board(), "A B C N O [R] Z");
+    }
+
+    #[test]
+    fn pangrams() {
+        init_logger();
+        let words = ["zabcornz", "carbon", "bronze"].map(String::from);
+        let bee = SpellingBee::new("abcnorz", 'z', WordTree::new(&words)).unwrap();
+        assert_eq!(bee.pangrams(), ["zabcornz"]);
+    }
+
+    #[test]
+    fn invalid_honeycombs() {
+        init_logger();
+        assert_eq!(
+            SpellingBee::new("abcdef", 'a', WordTree::default()).err(),
+            Some(InvalidHoneycomb::LetterCount(6))
+        );
+        assert_eq!(
+            SpellingBee::new("abcdefa", 'a', WordTree::default()).err(),
+            Some(InvalidHoneycomb::LetterCount(6))
+        );
+        assert_eq!(
+            SpellingBee::new("abcdefg", 'z', WordTree::default()).err(),
+            Some(InvalidHoneycomb::Centre('z'))
+        );
+        assert_eq!(
+            SpellingBee::new("ABCDEFG", 'A', WordTree::default()).err(),
+            Some(InvalidHoneycomb::Letter('A'))
+        );
+        assert_eq!(
+            SpellingBee::new("abcdefé", 'a', WordTree::default()).err(),
+            Some(InvalidHoneycomb::Letter('é'))
+        );
+        assert_eq!(
+            SpellingBee::new("abcdefg", 'é', WordTree::default()).err(),
+            Some(InvalidHoneycomb::Centre('é'))
+        );
+    }
+}
@@ -0,0 +1,103 @@
+use std::time::{Duration, Instant};
+
+/// A countdown that can be paused, for timed games.
+///
+/// Time only runs while the timer is not paused, so a paused game can be left for as long as
+/// needed.
+#[derive(Debug, Clone)]
+pub struct Timer {
+    limit: Duration,
+    /// Time used before the current run started.
+    used: Duration,
+    /// When the timer was last started or resumed, `None` while paused.
+    running_since: Option<Instant>,
+}
+
+impl Timer {
+    /// A running timer with `limit` on the clock.
+    pub fn new(limit: Duration) -> Self {
+        Timer {
+            limit,
+            used: Duration::ZERO,
+            running_since: Some(Instant::now()),
+        }
+    }
+
+    /// A paused timer that has already used `used` of its `limit`, e.g. when resuming a saved
+    /// game.
+    pub fn paused_at(limit: Duration, used: Duration) -> Self {
+        Timer {
+            limit,
+            used,
+            running_since: None,
+        }
+    }
+
+    pub fn limit(&self) -> Duration {
+        self.limit
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        match self.running_since {
+            Some(since) => self.used + since.elapsed(),
+            None => self.used,
+        }
+    }
+
+    pub fn remaining(&self) -> Duration {
+        self.limit.saturating_sub(self.elapsed())
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.elapsed() >= self.limit
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.running_since.is_none()
+    }
+
+    pub fn pause(&mut self) {
+        if let Some(since) = self.running_since.take() {
+            self.used += since.elapsed();
+        }
+    }
+
+    pub fn resume(&mut self) {
+        if self.running_since.is_none() {
+            self.running_since = Some(Instant::now());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::thread::sleep;
+
+    use super::*;
+
+    #[test]
+    fn counts_down() {
+        let timer = Timer::new(Duration::from_millis(20));
+        assert!(!timer.is_expired());
+        assert!(timer.remaining() <= Duration::from_millis(20));
+        sleep(Duration::from_millis(25));
+        assert!(timer.is_expired());
+        assert_eq!(timer.remaining(), Duration::ZERO);
+    }
+
+    #[test]
+    fn pausing_stops_the_clock() {
+        let mut timer = Timer::paused_at(Duration::from_secs(60), Duration::from_secs(10));
+        assert!(timer.is_paused());
+        sleep(Duration::from_millis(5));
+        assert_eq!(timer.elapsed(), Duration::from_secs(10));
+
+        timer.resume();
+        sleep(Duration::from_millis(5));
+        timer.pause();
+        let elapsed = timer.elapsed();
+        assert!(elapsed >= Duration::from_millis(10_005));
+        sleep(Duration::from_millis(5));
+        assert_eq!(timer.elapsed(), elapsed);
+    }
+}
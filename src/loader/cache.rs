@@ -0,0 +1,353 @@
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::{Instant, UNIX_EPOCH};
+
+use log::{info, warn};
+
+use super::report::LoadReport;
+use super::Loader;
+use crate::rng;
+use crate::word_tree::WordTree;
+
+/// Start of every cache file, bumped whenever the format changes.
+const HEADER: &[u8] = b"nine_q cache 1\n";
+
+/// Which version of a word list file a cached tree was built from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fingerprint {
+    pub size: u64,
+    /// Modification time, in nanoseconds since the Unix epoch.
+    pub modified: u64,
+    /// [`rng::hash`] of the file's contents, only compared once the size or modification time
+    /// has changed.
+    pub hash: u64,
+}
+
+/// Where the tree returned by [`TreeCache::load`] came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CacheStatus {
+    /// The word list has the same size and modification time, so it was not read at all.
+    Hit,
+    /// The word list was modified, but its contents are the same.
+    Unchanged,
+    /// There was no usable cached tree, so one was built from the word list and saved.
+    Built(LoadReport),
+}
+
+/// Keeps a prebuilt [`WordTree`] for each word list, so later runs can skip reading and
+/// filtering it.
+///
+/// A cached tree is used while the word list has the same size and modification time, without
+/// reading the list or checking its hash, so an edit that keeps both is not noticed. If either
+/// changes the list is read again, and the tree is only rebuilt if the contents have changed too.
+///
+/// Each loader configuration and version of the crate has its own trees, since they can build
+/// different trees from the same list. See [`Loader::cache_key`] for what tells them apart.
+#[derive(Debug, Clone)]
+pub struct TreeCache {
+    dir: PathBuf,
+    rebuild: bool,
+}
+
+impl Fingerprint {
+    fn write<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(HEADER)?;
+        for value in [self.size, self.modified, self.hash] {
+            writer.write_all(&value.to_le_bytes())?;
+        }
+        Ok(())
+    }
+
+    fn read<R: Read>(reader: &mut R) -> io::Result<Self> {
+        let mut header = [0; HEADER.len()];
+        reader.read_exact(&mut header)?;
+        if header != HEADER {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "not a nine_q cache file",
+            ));
+        }
+        let mut values = [0; 3];
+        for value in values.iter_mut() {
+            let mut bytes = [0; 8];
+            reader.read_exact(&mut bytes)?;
+            *value = u64::from_le_bytes(bytes);
+        }
+        let [size, modified, hash] = values;
+        Ok(Fingerprint {
+            size,
+            modified,
+            hash,
+        })
+    }
+}
+
+impl TreeCache {
+    pub fn new<P: AsRef<Path>>(dir: P) -> Self {
+        TreeCache {
+            dir: dir.as_ref().to_path_buf(),
+            rebuild: false,
+        }
+    }
+
+    /// `$XDG_CACHE_HOME/nine_q`, or `~/.cache/nine_q`, or a directory under the system's
+    /// temporary directory if neither is set.
+    pub fn default_dir() -> PathBuf {
+        env::var_os("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+            .unwrap_or_else(env::temp_dir)
+            .join("nine_q")
+    }
+
+    /// Ignores any cached trees, building them again from the word lists and saving them.
+    pub fn with_rebuild(mut self, rebuild: bool) -> Self {
+        self.rebuild = rebuild;
+        self
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Where the tree that `loader` builds from the word list at `path` is kept. `profile` names
+    /// the loader settings to make the cache easier to look through, the file name also has a
+    /// hash of [`Loader::cache_key`], which keys custom predicates by name only.
+    pub fn path_for<P: AsRef<Path>>(&self, path: P, profile: &str, loader: &Loader) -> PathBuf {
+        let path = path.as_ref();
+        let path = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        let name = format!(
+            "{}-{:016x}-{:016x}.tree",
+            profile,
+            rng::hash(path.to_string_lossy().as_bytes()),
+            rng::hash(loader.cache_key().as_bytes())
+        );
+        self.dir.join(name)
+    }
+
+    /// The tree for the word list at `path`, from the cache if it is still up to date, otherwise
+    /// built with `loader` and saved for next time.
+    ///
+    /// Problems with the cache itself are logged and the tree is built as if there was no cache,
+    /// only problems reading the word list are returned.
+    pub fn load<P: AsRef<Path>>(
+        &self,
+        path: P,
+        profile: &str,
+        loader: &Loader,
+    ) -> io::Result<(WordTree, CacheStatus)> {
+        let path = path.as_ref();
+        let start_time = Instant::now();
+        let cache_path = self.path_for(path, profile, loader);
+        let (size, modified) = stat(path)?;
+
+        let mut cached = match self.rebuild {
+            true => None,
+            false => open(&cache_path),
+        };
+
+        // Same size and modification time, trust that the contents have not changed
+        if let Some((fingerprint, _)) = &cached {
+            if fingerprint.size == size && fingerprint.modified == modified {
+                let (_, reader) = cached.take().unwrap();
+                if let Some(tree) = read_tree(&cache_path, reader) {
+                    let duration = start_time.elapsed();
+                    info!(
+                        "Took {}.{:03}s to load cached tree for {}",
+                        duration.as_secs(),
+                        duration.subsec_millis(),
+                        path.display()
+                    );
+                    return Ok((tree, CacheStatus::Hit));
+                }
+            }
+        }
+
+        let text = fs::read_to_string(path)?;
+        let fingerprint = Fingerprint {
+            size,
+            modified,
+            hash: rng::hash(text.as_bytes()),
+        };
+
+        if let Some((cached_fingerprint, reader)) = cached {
+            if cached_fingerprint.size == size && cached_fingerprint.hash == fingerprint.hash {
+                if let Some(tree) = read_tree(&cache_path, reader) {
+                    info!("{} was touched but has not changed", path.display());
+                    // Save the new modification time so the next run can skip reading the list
+                    save(&cache_path, &fingerprint, &tree);
+                    return Ok((tree, CacheStatus::Unchanged));
+                }
+            }
+        }
+
+        info!("Building tree for {}", path.display());
+        let (tree, report) = loader.load_str(&text);
+        save(&cache_path, &fingerprint, &tree);
+        Ok((tree, CacheStatus::Built(report)))
+    }
+}
+
+/// Size and modification time of the file at `path`.
+fn stat(path: &Path) -> io::Result<(u64, u64)> {
+    let metadata = fs::metadata(path)?;
+    let modified = metadata
+        .modified()?
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or_default();
+    Ok((metadata.len(), modified))
+}
+
+fn open(cache_path: &Path) -> Option<(Fingerprint, BufReader<File>)> {
+    let result = File::open(cache_path).and_then(|file| {
+        let mut reader = BufReader::new(file);
+        Ok((Fingerprint::read(&mut reader)?, reader))
+    });
+    match result {
+        Ok(cached) => Some(cached),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+        Err(e) => {
+            warn!("Ignoring cached tree {}: {}", cache_path.display(), e);
+            None
+        }
+    }
+}
+
+fn read_tree(cache_path: &Path, reader: BufReader<File>) -> Option<WordTree> {
+    match WordTree::read(reader) {
+        Ok(tree) => Some(tree),
+        Err(e) => {
+            warn!("Ignoring cached tree {}: {}", cache_path.display(), e);
+            None
+        }
+    }
+}
+
+/// Writes the tree next to `cache_path` and moves it into place, so that an interrupted run
+/// never leaves half a tree behind. The temporary file is named after the process, so two runs
+/// saving at once do not write into the same file.
+fn save(cache_path: &Path, fingerprint: &Fingerprint, tree: &WordTree) {
+    let temp_path = cache_path.with_extension(format!("{}.tmp", process::id()));
+    let result = (|| {
+        if let Some(dir) = cache_path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut writer = BufWriter::new(File::create(&temp_path)?);
+        fingerprint.write(&mut writer)?;
+        tree.write(writer)?;
+        fs::rename(&temp_path, cache_path)
+    })();
+    match result {
+        Ok(()) => info!("Cached tree in {}", cache_path.display()),
+        Err(e) => {
+            warn!("Could not cache tree in {}: {}", cache_path.display(), e);
+            let _ = fs::remove_file(temp_path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, SystemTime};
+
+    use crate::init_logger;
+    use crate::loader::filter::Filter;
+
+    use super::*;
+
+    #[test]
+    fn reuses_until_changed() {
+        init_logger();
+        let dir = env::temp_dir().join(format!("nine_q_cache_{}", std::process::id()));
+        let path = dir.join("words.txt");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(&path, "kind\nkin\nkinder\n").unwrap();
+        let cache = TreeCache::new(dir.join("cache"));
+        let loader = Loader::nine_letter();
+
+        let (tree, status) = cache.load(&path, "nine_letter", &loader).unwrap();
+        assert!(matches!(status, CacheStatus::Built(report) if report.accepted == 2));
+        assert!(tree.contains("kinder"));
+        assert!(cache.path_for(&path, "nine_letter", &loader).exists());
+
+        let (tree, status) = cache.load(&path, "nine_letter", &loader).unwrap();
+        assert_eq!(status, CacheStatus::Hit);
+        assert!(tree.contains("kinder"));
+        assert!(!tree.contains("kin"));
+
+        // Another profile has its own tree
+        let (tree, status) = cache.load(&path, "everything", &Loader::new()).unwrap();
+        assert!(matches!(status, CacheStatus::Built(_)));
+        assert!(tree.contains("kin"));
+
+        // So do other settings under the same profile name
+        let longer = Loader::nine_letter().with_filter(Filter::new().with_lengths(5..=9));
+        assert_ne!(
+            cache.path_for(&path, "nine_letter", &longer),
+            cache.path_for(&path, "nine_letter", &loader)
+        );
+        let (tree, status) = cache.load(&path, "nine_letter", &longer).unwrap();
+        assert!(matches!(status, CacheStatus::Built(_)));
+        assert!(!tree.contains("kind"));
+        let (_, status) = cache.load(&path, "nine_letter", &loader).unwrap();
+        assert_eq!(status, CacheStatus::Hit);
+
+        // Allowed characters are told apart by what they allow, not just whether there are any
+        let vowels = Loader::nine_letter()
+            .with_filter(Filter::nine_letter().with_allowed_chars(|c| "aeiou".contains(c)));
+        assert_ne!(
+            cache.path_for(&path, "nine_letter", &vowels),
+            cache.path_for(&path, "nine_letter", &loader)
+        );
+
+        let touch = |time: SystemTime| {
+            File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(time)
+                .unwrap()
+        };
+        touch(SystemTime::now() + Duration::from_secs(60));
+        let (_, status) = cache.load(&path, "nine_letter", &loader).unwrap();
+        assert_eq!(status, CacheStatus::Unchanged);
+        let (_, status) = cache.load(&path, "nine_letter", &loader).unwrap();
+        assert_eq!(status, CacheStatus::Hit);
+
+        fs::write(&path, "kind\nkin\nkindest\n").unwrap();
+        touch(SystemTime::now() + Duration::from_secs(120));
+        let (tree, status) = cache.load(&path, "nine_letter", &loader).unwrap();
+        assert!(matches!(status, CacheStatus::Built(_)));
+        assert!(tree.contains("kindest"));
+        assert!(!tree.contains("kinder"));
+
+        let (_, status) = cache
+            .clone()
+            .with_rebuild(true)
+            .load(&path, "nine_letter", &loader)
+            .unwrap();
+        assert!(matches!(status, CacheStatus::Built(_)));
+
+        fs::write(cache.path_for(&path, "nine_letter", &loader), "not a tree").unwrap();
+        let (tree, status) = cache.load(&path, "nine_letter", &loader).unwrap();
+        assert!(matches!(status, CacheStatus::Built(_)));
+        assert!(tree.contains("kindest"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn missing_word_list() {
+        init_logger();
+        let dir = env::temp_dir().join(format!("nine_q_cache_missing_{}", std::process::id()));
+        let error = TreeCache::new(&dir)
+            .load(dir.join("words.txt"), "nine_letter", &Loader::new())
+            .map(|_| ())
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+    }
+}
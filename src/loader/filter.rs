@@ -0,0 +1,206 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::sync::Arc;
+
+type Predicate = Arc<dyn Fn(&str) -> bool + Send + Sync>;
+
+/// Characters checked against [`Filter::with_allowed_chars`] by [`Filter::cache_key`], up to the
+/// end of Latin Extended-B.
+pub const SAMPLED_CHARS: u32 = 0x250;
+
+/// Decides which lines of a word list make it into the tree.
+///
+/// Lengths are counted in characters, not bytes.
+#[derive(Clone)]
+pub struct Filter {
+    lengths: RangeInclusive<usize>,
+    allowed: Option<Arc<dyn Fn(char) -> bool + Send + Sync>>,
+    predicates: Vec<(String, Predicate)>,
+}
+
+/// Why a [`Filter`] turned a word down.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rejection {
+    /// The word has this many characters, which is outside the filter's range.
+    Length(usize),
+    /// The word has a character the filter does not allow.
+    Character(char),
+    /// A custom predicate, given by the name it was added with, returned false.
+    Predicate(String),
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rejection::Length(n) => write!(f, "{} characters long", n),
+            Rejection::Character(c) => write!(f, "contains {:?}", c),
+            Rejection::Predicate(name) => write!(f, "failed {}", name),
+        }
+    }
+}
+
+impl Default for Filter {
+    /// Accepts any non-empty line.
+    fn default() -> Self {
+        Filter {
+            lengths: 1..=usize::MAX,
+            allowed: None,
+            predicates: Vec::new(),
+        }
+    }
+}
+
+impl fmt::Debug for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Filter")
+            .field("lengths", &self.lengths)
+            .field("allowed", &self.allowed.is_some())
+            .field(
+                "predicates",
+                &self.predicates.iter().map(|(n, _)| n).collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl Filter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Words of 4 to 9 letters from `a` to `z`, for nine letter puzzles.
+    pub fn nine_letter() -> Self {
+        Self::new()
+            .with_lengths(4..=9)
+            .with_allowed_chars(|c| c.is_ascii_lowercase())
+    }
+
+    pub fn with_lengths(mut self, lengths: RangeInclusive<usize>) -> Self {
+        self.lengths = lengths;
+        self
+    }
+
+    /// Only accepts words made entirely of characters `allowed` returns true for.
+    pub fn with_allowed_chars<F>(mut self, allowed: F) -> Self
+    where
+        F: Fn(char) -> bool + Send + Sync + 'static,
+    {
+        self.allowed = Some(Arc::new(allowed));
+        self
+    }
+
+    /// Adds a check every word has to pass. `name` is used when reporting what it turned down.
+    pub fn with_predicate<F>(mut self, name: &str, predicate: F) -> Self
+    where
+        F: Fn(&str) -> bool + Send + Sync + 'static,
+    {
+        self.predicates
+            .push((name.to_string(), Arc::new(predicate)));
+        self
+    }
+
+    pub fn check(&self, word: &str) -> Result<(), Rejection> {
+        let length = word.chars().count();
+        if !self.lengths.contains(&length) {
+            return Err(Rejection::Length(length));
+        }
+        if let Some(allowed) = &self.allowed {
+            if let Some(c) = word.chars().find(|c| !allowed(*c)) {
+                return Err(Rejection::Character(c));
+            }
+        }
+        match self.predicates.iter().find(|(_, p)| !p(word)) {
+            Some((name, _)) => Err(Rejection::Predicate(name.clone())),
+            None => Ok(()),
+        }
+    }
+
+    /// Describes the filter for [`super::Loader::cache_key`]. Closures can not be compared, so
+    /// the allowed characters are recorded as which of the first [`SAMPLED_CHARS`] characters
+    /// pass, and predicates only by name.
+    pub fn cache_key(&self) -> String {
+        let allowed = match &self.allowed {
+            Some(allowed) => (0..SAMPLED_CHARS)
+                .filter_map(char::from_u32)
+                .filter(|c| allowed(*c))
+                .collect::<String>(),
+            None => "any".to_string(),
+        };
+        let names = self
+            .predicates
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<&str>>();
+        format!("{:?} {:?} {:?}", self.lengths, allowed, names)
+    }
+
+    pub fn accepts(&self, word: &str) -> bool {
+        self.check(word).is_ok()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn cache_keys() {
+        let nine_letter = Filter::nine_letter().cache_key();
+        assert_eq!(nine_letter, Filter::nine_letter().cache_key());
+        assert_ne!(nine_letter, Filter::new().with_lengths(4..=9).cache_key());
+        assert_ne!(
+            nine_letter,
+            Filter::nine_letter()
+                .with_allowed_chars(|c| c.is_ascii_alphabetic())
+                .cache_key()
+        );
+        // Predicates can only be told apart by name
+        let named = |name: &str| Filter::new().with_predicate(name, |_| true).cache_key();
+        assert_eq!(
+            named("short"),
+            Filter::new().with_predicate("short", |_| false).cache_key()
+        );
+        assert_ne!(named("short"), named("long"));
+    }
+
+    #[test]
+    fn nine_letter() {
+        let filter = Filter::nine_letter();
+        assert!(filter.accepts("kind"));
+        assert!(filter.accepts("aspersion"));
+        assert_eq!(filter.check("kin"), Err(Rejection::Length(3)));
+        assert_eq!(filter.check("apertures"), Ok(()));
+        assert_eq!(filter.check("naïve"), Err(Rejection::Character('ï')));
+        // Nine letters but ten bytes
+        assert_eq!(filter.check("crêpeline"), Err(Rejection::Character('ê')));
+        assert_eq!(filter.check("Kind"), Err(Rejection::Character('K')));
+    }
+
+    #[test]
+    fn character_lengths() {
+        let filter = Filter::new().with_lengths(4..=5);
+        assert!(filter.accepts("crêpe"));
+        assert!(!filter.accepts("crêpes"));
+        assert!(!filter.accepts(""));
+    }
+
+    #[test]
+    fn predicates() {
+        let filter = Filter::new()
+            .with_predicate("no q without u", |w| !w.contains('q') || w.contains("qu"))
+            .with_predicate("no triple letters", |w| {
+                !w.as_bytes()
+                    .windows(3)
+                    .any(|b| b[0] == b[1] && b[1] == b[2])
+            });
+        assert!(filter.accepts("quiz"));
+        assert_eq!(
+            filter.check("qat"),
+            Err(Rejection::Predicate("no q without u".to_string()))
+        );
+        assert_eq!(
+            filter.check("brrr"),
+            Err(Rejection::Predicate("no triple letters".to_string()))
+        );
+    }
+}
@@ -0,0 +1,714 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use log::{info, warn};
+
+use super::report::LoadReport;
+use super::source::Source;
+use super::Loader;
+use crate::word_tree::WordTree;
+
+/// A flag from a Hunspell `.aff` file, however the file chose to write it.
+type Flag = u64;
+
+/// Expands Hunspell dictionaries, a `.dic` word list plus the `.aff` prefix and suffix rules that
+/// go with it, into every surface form they describe.
+///
+/// Covers the parts of the format that make words: `SET`, `FLAG`, `AF`, `PFX`, `SFX` (including
+/// a second suffix from a suffix's own flags), `NEEDAFFIX`, `FORBIDDENWORD` and `ONLYINCOMPOUND`.
+/// Compounding rules are not followed, so only words that stand on their own are produced.
+#[derive(Debug, Clone)]
+pub struct Hunspell {
+    skip_forbidden: bool,
+    skip_only_in_compound: bool,
+}
+
+impl Default for Hunspell {
+    /// Skips forbidden words and those only allowed inside compounds.
+    fn default() -> Self {
+        Hunspell {
+            skip_forbidden: true,
+            skip_only_in_compound: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum FlagType {
+    /// One character per flag.
+    #[default]
+    Single,
+    /// Two characters per flag.
+    Long,
+    /// Numbers separated by commas.
+    Numeric,
+}
+
+#[derive(Debug, Clone)]
+enum CharClass {
+    Any,
+    OneOf(Vec<char>),
+    NoneOf(Vec<char>),
+}
+
+/// One `PFX` or `SFX` rule.
+#[derive(Debug, Clone)]
+struct Affix {
+    strip: String,
+    add: String,
+    /// Checked against the start of the word for prefixes and the end for suffixes, before
+    /// `strip` is taken off.
+    condition: Vec<CharClass>,
+    /// Flags the affixed word gets, e.g. for a second suffix.
+    flags: Vec<Flag>,
+    /// Whether a prefix and a suffix can both be added to a word, from the rule's header.
+    cross_product: bool,
+}
+
+/// The rules under one `PFX` or `SFX` header.
+#[derive(Debug, Clone)]
+struct AffixGroup {
+    cross_product: bool,
+    rules: Vec<Affix>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Affixes {
+    flag_type: FlagType,
+    /// `AF` lines, which `.dic` entries can refer to by number instead of listing flags.
+    aliases: Vec<Vec<Flag>>,
+    /// Set by the first `AF` line, which says how many aliases follow.
+    alias_count: Option<usize>,
+    prefixes: HashMap<Flag, AffixGroup>,
+    suffixes: HashMap<Flag, AffixGroup>,
+    forbidden: Option<Flag>,
+    only_in_compound: Option<Flag>,
+    need_affix: Option<Flag>,
+}
+
+impl Hunspell {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Leaves out words marked `FORBIDDENWORD`, along with their affixed forms.
+    pub fn with_skip_forbidden(mut self, skip_forbidden: bool) -> Self {
+        self.skip_forbidden = skip_forbidden;
+        self
+    }
+
+    /// Leaves out words and affixed forms marked `ONLYINCOMPOUND`.
+    pub fn with_skip_only_in_compound(mut self, skip_only_in_compound: bool) -> Self {
+        self.skip_only_in_compound = skip_only_in_compound;
+        self
+    }
+
+    /// Every distinct word in the dictionary at `dic`, using the rules in `aff`. Both files are
+    /// decoded with the `.aff` file's `SET`, which can be UTF-8 or ISO8859-1.
+    pub fn read_words<P: AsRef<Path>, Q: AsRef<Path>>(
+        &self,
+        dic: P,
+        aff: Q,
+    ) -> io::Result<Vec<String>> {
+        Ok(strip_lines(self.read_entries(dic, aff)?))
+    }
+
+    /// Like [`Self::read_words`], with the `.dic` line (counting from 1) each word was first
+    /// expanded from.
+    pub fn read_entries<P: AsRef<Path>, Q: AsRef<Path>>(
+        &self,
+        dic: P,
+        aff: Q,
+    ) -> io::Result<Vec<(usize, String)>> {
+        info!(
+            "Reading Hunspell dictionary {} with {}",
+            dic.as_ref().display(),
+            aff.as_ref().display()
+        );
+        let aff = fs::read(aff)?;
+        let encoding = encoding(&aff);
+        self.expand_entries(
+            &decode(fs::read(dic)?, &encoding)?,
+            &decode(aff, &encoding)?,
+        )
+    }
+
+    /// Like [`Self::read_words`], for a dictionary already in memory.
+    pub fn expand(&self, dic: &str, aff: &str) -> io::Result<Vec<String>> {
+        Ok(strip_lines(self.expand_entries(dic, aff)?))
+    }
+
+    /// Like [`Self::read_entries`], for a dictionary already in memory.
+    pub fn expand_entries(&self, dic: &str, aff: &str) -> io::Result<Vec<(usize, String)>> {
+        let affixes = Affixes::parse(aff)?;
+        let mut entries = Vec::new();
+
+        let mut lines = dic.lines().enumerate().peekable();
+        // The first line is (roughly) how many entries follow
+        if let Some((_, line)) = lines.peek() {
+            if line
+                .trim()
+                .trim_start_matches('\u{feff}')
+                .parse::<usize>()
+                .is_ok()
+            {
+                lines.next();
+            }
+        }
+        for (i, line) in lines {
+            let Some((stem, flags)) = split_entry(line) else {
+                continue;
+            };
+            let flags = affixes
+                .parse_entry_flags(flags)
+                .map_err(|e| invalid(&format!("line {} of .dic: {}", i + 1, e)))?;
+            entries.push((i + 1, stem, flags));
+        }
+
+        // A forbidden entry also rules out the same word made from another stem's affixes, so
+        // they have to be known before expanding anything
+        let forbidden = match self.skip_forbidden {
+            true => entries
+                .iter()
+                .filter(|(_, _, flags)| affixes.has(flags, affixes.forbidden))
+                .map(|(_, stem, _)| stem.clone())
+                .collect(),
+            false => HashSet::new(),
+        };
+
+        let mut seen = HashSet::new();
+        let mut words = Vec::new();
+        for (line, stem, flags) in &entries {
+            for word in self.forms(&affixes, stem, flags) {
+                if !forbidden.contains(&word) && seen.insert(word.clone()) {
+                    words.push((*line, word));
+                }
+            }
+        }
+
+        info!(
+            "Expanded {} dictionary entries into {} words",
+            entries.len(),
+            words.len()
+        );
+        Ok(words)
+    }
+
+    /// Expands the dictionary and loads the words with `loader`, which normalizes and filters
+    /// them as it would any other word list. Rejected words are reported with the `.dic` line
+    /// they were expanded from.
+    pub fn load<P: AsRef<Path>, Q: AsRef<Path>>(
+        &self,
+        dic: P,
+        aff: Q,
+        loader: &Loader,
+    ) -> io::Result<(WordTree, LoadReport)> {
+        let source = Source::Hunspell {
+            dic: dic.as_ref().to_path_buf(),
+            aff: aff.as_ref().to_path_buf(),
+        };
+        loader
+            .clone()
+            .with_hunspell(self.clone())
+            .load_from(&source)
+    }
+
+    /// Every form of one `.dic` entry.
+    fn forms(&self, affixes: &Affixes, stem: &str, flags: &[Flag]) -> Vec<String> {
+        let mut forms = Vec::new();
+        if self.excluded(affixes, flags) {
+            return forms;
+        }
+
+        // The stem and each suffixed word, with the flags it has and whether it can be prefixed
+        let mut suffixed = vec![(stem.to_string(), flags.to_vec(), true)];
+        for suffix in affixes.suffixes(flags).filter(|s| s.applies_to_end(stem)) {
+            let word = suffix.apply_to_end(stem);
+            for second in affixes
+                .suffixes(&suffix.flags)
+                .filter(|s| s.applies_to_end(&word))
+            {
+                suffixed.push((
+                    second.apply_to_end(&word),
+                    second.flags.clone(),
+                    suffix.cross_product && second.cross_product,
+                ));
+            }
+            suffixed.push((word, suffix.flags.clone(), suffix.cross_product));
+        }
+        for (word, flags, _) in &suffixed {
+            if !self.excluded(affixes, flags) && !affixes.has(flags, affixes.need_affix) {
+                forms.push(word.clone());
+            }
+        }
+
+        for prefix in affixes.prefixes(flags) {
+            if self.excluded(affixes, &prefix.flags) {
+                continue;
+            }
+            if prefix.applies_to_start(stem) {
+                forms.push(prefix.apply_to_start(stem));
+            }
+            if !prefix.cross_product {
+                continue;
+            }
+            for (word, flags, cross_product) in &suffixed[1..] {
+                if *cross_product && !self.excluded(affixes, flags) && prefix.applies_to_start(word)
+                {
+                    forms.push(prefix.apply_to_start(word));
+                }
+            }
+        }
+        forms
+    }
+
+    fn excluded(&self, affixes: &Affixes, flags: &[Flag]) -> bool {
+        (self.skip_forbidden && affixes.has(flags, affixes.forbidden))
+            || (self.skip_only_in_compound && affixes.has(flags, affixes.only_in_compound))
+    }
+}
+
+impl Affixes {
+    fn parse(aff: &str) -> io::Result<Self> {
+        let mut affixes = Affixes::default();
+        for (i, line) in aff.lines().enumerate() {
+            affixes
+                .parse_line(line.trim_start_matches('\u{feff}'))
+                .map_err(|e| invalid(&format!("line {} of .aff: {}", i + 1, e)))?;
+        }
+        Ok(affixes)
+    }
+
+    fn parse_line(&mut self, line: &str) -> Result<(), String> {
+        let fields = line.split_whitespace().collect::<Vec<&str>>();
+        match fields[..] {
+            ["FLAG", flag_type] => {
+                self.flag_type = match flag_type {
+                    "long" => FlagType::Long,
+                    "num" => FlagType::Numeric,
+                    "UTF-8" => FlagType::Single,
+                    _ => return Err(format!("unknown flag type {}", flag_type)),
+                }
+            }
+            ["AF", count] if self.alias_count.is_none() => {
+                self.alias_count = Some(
+                    count
+                        .parse()
+                        .map_err(|_| format!("bad alias count {:?}", count))?,
+                )
+            }
+            ["AF", flags, ..] => {
+                let flags = self.parse_flags(flags)?;
+                self.aliases.push(flags);
+            }
+            ["FORBIDDENWORD", flag] => self.forbidden = Some(self.parse_flag(flag)?),
+            ["ONLYINCOMPOUND", flag] => self.only_in_compound = Some(self.parse_flag(flag)?),
+            ["NEEDAFFIX", flag] | ["PSEUDOROOT", flag] => {
+                self.need_affix = Some(self.parse_flag(flag)?)
+            }
+            // A header line, saying whether the rules cross with the other kind of affix
+            [kind @ ("PFX" | "SFX"), flag, cross_product @ ("Y" | "N"), count]
+                if count.parse::<usize>().is_ok() =>
+            {
+                let flag = self.parse_flag(flag)?;
+                let group = AffixGroup {
+                    cross_product: cross_product == "Y",
+                    rules: Vec::new(),
+                };
+                match kind {
+                    "PFX" => self.prefixes.insert(flag, group),
+                    _ => self.suffixes.insert(flag, group),
+                };
+            }
+            [kind @ ("PFX" | "SFX"), flag, strip, add, ref rest @ ..] => {
+                let flag = self.parse_flag(flag)?;
+                let (add, flags) = match add.split_once('/') {
+                    Some((add, flags)) => (add, self.parse_entry_flags(flags)?),
+                    None => (add, Vec::new()),
+                };
+                let condition = parse_condition(rest.first().copied().unwrap_or("."))?;
+                let group = match kind {
+                    "PFX" => self.prefixes.get_mut(&flag),
+                    _ => self.suffixes.get_mut(&flag),
+                }
+                .ok_or_else(|| format!("{} rule before its header", kind))?;
+                group.rules.push(Affix {
+                    strip: zero_is_empty(strip),
+                    add: zero_is_empty(add),
+                    condition,
+                    flags,
+                    cross_product: group.cross_product,
+                });
+            }
+            // Everything else is about spelling suggestions, compounds or morphology
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn parse_flag(&self, flag: &str) -> Result<Flag, String> {
+        match self.parse_flags(flag)?[..] {
+            [flag] => Ok(flag),
+            _ => Err(format!("expected one flag, found {:?}", flag)),
+        }
+    }
+
+    fn parse_flags(&self, flags: &str) -> Result<Vec<Flag>, String> {
+        match self.flag_type {
+            FlagType::Single => Ok(flags.chars().map(|c| c as Flag).collect()),
+            FlagType::Long => {
+                let chars = flags.chars().collect::<Vec<char>>();
+                if chars.len() % 2 != 0 {
+                    return Err(format!(
+                        "odd number of characters in long flags {:?}",
+                        flags
+                    ));
+                }
+                Ok(chars
+                    .chunks(2)
+                    .map(|pair| ((pair[0] as Flag) << 32) | pair[1] as Flag)
+                    .collect())
+            }
+            FlagType::Numeric => flags
+                .split(',')
+                .map(|n| n.parse::<Flag>())
+                .collect::<Result<Vec<Flag>, _>>()
+                .map_err(|_| format!("bad numeric flags {:?}", flags)),
+        }
+    }
+
+    /// Flags in a `.dic` entry or after an affix's `/`, which may be an `AF` alias number.
+    fn parse_entry_flags(&self, flags: &str) -> Result<Vec<Flag>, String> {
+        if self.aliases.is_empty() {
+            return self.parse_flags(flags);
+        }
+        // Aliases count from 1
+        flags
+            .parse::<usize>()
+            .ok()
+            .and_then(|n| self.aliases.get(n.checked_sub(1)?))
+            .cloned()
+            .ok_or_else(|| format!("unknown flag alias {:?}", flags))
+    }
+
+    fn has(&self, flags: &[Flag], flag: Option<Flag>) -> bool {
+        flag.is_some_and(|flag| flags.contains(&flag))
+    }
+
+    fn prefixes<'a>(&'a self, flags: &'a [Flag]) -> impl Iterator<Item = &'a Affix> {
+        rules(&self.prefixes, flags)
+    }
+
+    fn suffixes<'a>(&'a self, flags: &'a [Flag]) -> impl Iterator<Item = &'a Affix> {
+        rules(&self.suffixes, flags)
+    }
+}
+
+/// The rules for each of `flags`.
+fn rules<'a>(
+    groups: &'a HashMap<Flag, AffixGroup>,
+    flags: &'a [Flag],
+) -> impl Iterator<Item = &'a Affix> {
+    flags
+        .iter()
+        .filter_map(|flag| groups.get(flag))
+        .flat_map(|group| group.rules.iter())
+}
+
+impl Affix {
+    fn applies_to_start(&self, word: &str) -> bool {
+        let chars = word.chars().collect::<Vec<char>>();
+        word.len() > self.strip.len()
+            && word.starts_with(&self.strip)
+            && chars.len() >= self.condition.len()
+            && matches(&self.condition, &chars[..self.condition.len()])
+    }
+
+    fn applies_to_end(&self, word: &str) -> bool {
+        let chars = word.chars().collect::<Vec<char>>();
+        word.len() > self.strip.len()
+            && word.ends_with(&self.strip)
+            && chars.len() >= self.condition.len()
+            && matches(
+                &self.condition,
+                &chars[chars.len() - self.condition.len()..],
+            )
+    }
+
+    fn apply_to_start(&self, word: &str) -> String {
+        format!("{}{}", self.add, &word[self.strip.len()..])
+    }
+
+    fn apply_to_end(&self, word: &str) -> String {
+        format!("{}{}", &word[..word.len() - self.strip.len()], self.add)
+    }
+}
+
+/// Whether each of `chars` matches the class in the same place in `condition`.
+fn matches(condition: &[CharClass], chars: &[char]) -> bool {
+    condition.iter().zip(chars).all(|(class, c)| match class {
+        CharClass::Any => true,
+        CharClass::OneOf(set) => set.contains(c),
+        CharClass::NoneOf(set) => !set.contains(c),
+    })
+}
+
+/// A condition such as `.`, `y` or `[^aeiou]y`.
+fn parse_condition(condition: &str) -> Result<Vec<CharClass>, String> {
+    let mut classes = Vec::new();
+    let mut chars = condition.chars();
+    while let Some(c) = chars.next() {
+        classes.push(match c {
+            '.' => CharClass::Any,
+            '[' => {
+                let mut set = Vec::new();
+                let mut closed = false;
+                for c in chars.by_ref() {
+                    if c == ']' {
+                        closed = true;
+                        break;
+                    }
+                    set.push(c);
+                }
+                if !closed {
+                    return Err(format!("unclosed '[' in condition {:?}", condition));
+                }
+                match set.first() {
+                    Some('^') => CharClass::NoneOf(set[1..].to_vec()),
+                    _ => CharClass::OneOf(set),
+                }
+            }
+            c => CharClass::OneOf(vec![c]),
+        });
+    }
+    Ok(classes)
+}
+
+/// The word and flags of a `.dic` line, `None` for lines without a word.
+fn split_entry(line: &str) -> Option<(String, &str)> {
+    // Anything after whitespace is morphological data
+    let entry = line.split(['\t', ' ']).next()?;
+    if entry.is_empty() || entry.starts_with('#') {
+        return None;
+    }
+    // A slash that is part of the word is escaped
+    let mut word = String::new();
+    let mut chars = entry.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' if entry[i + 1..].starts_with('/') => {
+                word.push('/');
+                chars.next();
+            }
+            '/' => return Some((word, &entry[i + 1..])),
+            c => word.push(c),
+        }
+    }
+    Some((word, ""))
+}
+
+fn strip_lines(entries: Vec<(usize, String)>) -> Vec<String> {
+    entries.into_iter().map(|(_, word)| word).collect()
+}
+
+fn zero_is_empty(field: &str) -> String {
+    match field {
+        "0" => String::new(),
+        field => field.to_string(),
+    }
+}
+
+/// The `SET` encoding of an `.aff` file, which Hunspell assumes is ISO8859-1 if not given.
+fn encoding(aff: &[u8]) -> String {
+    String::from_utf8_lossy(aff)
+        .lines()
+        .find_map(
+            |line| match line.split_whitespace().collect::<Vec<&str>>()[..] {
+                ["SET", encoding] => Some(encoding.to_string()),
+                _ => None,
+            },
+        )
+        .unwrap_or_else(|| "ISO8859-1".to_string())
+}
+
+fn decode(bytes: Vec<u8>, encoding: &str) -> io::Result<String> {
+    match encoding.to_ascii_uppercase().as_str() {
+        "UTF-8" => String::from_utf8(bytes).map_err(|_| invalid("not valid UTF-8")),
+        "ISO8859-1" | "ISO-8859-1" => Ok(bytes.into_iter().map(char::from).collect()),
+        _ => {
+            warn!("Hunspell encoding {} is not supported", encoding);
+            Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!("Hunspell encoding {} is not supported", encoding),
+            ))
+        }
+    }
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("Invalid Hunspell dictionary: {}", message),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use crate::init_logger;
+
+    use super::*;
+
+    const AFF: &str = "\
+SET UTF-8
+TRY esianrtolcdugmphbyfvkwzESIANRTOLCDUGMPHBYFVKWZ'
+FORBIDDENWORD !
+ONLYINCOMPOUND c
+NEEDAFFIX n
+
+# Prefixes
+PFX A Y 1
+PFX A 0 re .
+
+PFX U N 1
+PFX U 0 un .
+
+SFX B Y 3
+SFX B 0 ed [^y]
+SFX B y ied [^aeiou]y
+SFX B 0 ed [aeiou]y
+
+SFX S Y 2
+SFX S 0 s [^s]
+SFX S 0 es s
+
+SFX D Y 1
+SFX D 0 ness/S .
+
+SFX L Y 1
+SFX L 0 less .
+";
+
+    fn sorted(mut words: Vec<String>) -> Vec<String> {
+        words.sort();
+        words
+    }
+
+    #[test]
+    fn expand() {
+        init_logger();
+        let dic = "7\nwork/AB\ncry/B\nplay/B\nkind/DU\nruth/nL\nkindnes/!\nwort/c\n";
+        let words = Hunspell::new().expand(dic, AFF).unwrap();
+        assert_eq!(
+            sorted(words),
+            [
+                "cried",
+                "cry",
+                "kind",
+                "kindness",
+                "kindnesses",
+                "play",
+                "played",
+                "rework",
+                "reworked",
+                "ruthless",
+                "unkind",
+                "work",
+                "worked",
+            ]
+        );
+
+        let words = Hunspell::new()
+            .with_skip_forbidden(false)
+            .with_skip_only_in_compound(false)
+            .expand(dic, AFF)
+            .unwrap();
+        assert!(words.contains(&"kindnes".to_string()));
+        assert!(words.contains(&"wort".to_string()));
+
+        // Forbidding a word also drops it when another stem's suffix makes it
+        let dic = "2\nkind/D\nkindness/!\n";
+        let words = Hunspell::new().expand(dic, AFF).unwrap();
+        assert_eq!(sorted(words), ["kind", "kindnesses"]);
+        let words = Hunspell::new()
+            .with_skip_forbidden(false)
+            .expand(dic, AFF)
+            .unwrap();
+        assert!(words.contains(&"kindness".to_string()));
+    }
+
+    #[test]
+    fn flag_types() {
+        init_logger();
+        let aff = "FLAG long\nSFX Aa Y 1\nSFX Aa 0 s .\nPFX Bb N 1\nPFX Bb 0 un . po:neg\n";
+        let words = Hunspell::new().expand("kind/AaBb\tst:kind\n", aff).unwrap();
+        assert_eq!(sorted(words), ["kind", "kinds", "unkind"]);
+
+        let aff = "FLAG num\nAF 2\nAF 1,2 # kinds and unkind\nAF 2\n\
+                   SFX 1 Y 1\nSFX 1 0 s .\nPFX 2 N 1\nPFX 2 0 un .\n";
+        let words = Hunspell::new().expand("kind/1\nwell/2\n", aff).unwrap();
+        assert_eq!(sorted(words), ["kind", "kinds", "unkind", "unwell", "well"]);
+
+        let error = Hunspell::new().expand("kind/3\n", aff).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        let error = Hunspell::new()
+            .expand("kind\n", "SFX A 0 s .\n")
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn load() {
+        init_logger();
+        let dir = std::env::temp_dir();
+        let dic = dir.join(format!("nine_q_hunspell_{}.dic", std::process::id()));
+        let aff = dic.with_extension("aff");
+        // Hunspell's default encoding, with é as a single byte
+        fs::write(&aff, b"SFX S Y 1\nSFX S 0 s .\n").unwrap();
+        fs::write(&dic, b"2\ncaf\xe9/S\nkind/S\n").unwrap();
+
+        let words = Hunspell::new().read_words(&dic, &aff).unwrap();
+        assert_eq!(sorted(words), ["café", "cafés", "kind", "kinds"]);
+
+        let (tree, report) = Hunspell::new()
+            .load(&dic, &aff, &Loader::nine_letter())
+            .unwrap();
+        assert!(tree.contains("cafes"));
+        assert!(tree.contains("kinds"));
+        assert_eq!(report.accepted, 4);
+
+        // The same words as a layer source
+        let (words, _) = Loader::nine_letter()
+            .read_from(&Source::hunspell(&dic))
+            .unwrap();
+        assert_eq!(sorted(words), ["cafe", "cafes", "kind", "kinds"]);
+
+        // Rejected words point at the entry they were expanded from
+        fs::write(&aff, "FORBIDDENWORD !\nSFX S Y 1\nSFX S 0 s .\n").unwrap();
+        fs::write(&dic, "3\nkind/S\nab/S\nkindly/!\n").unwrap();
+        let (_, report) = Loader::nine_letter()
+            .read_from(&Source::hunspell(&dic))
+            .unwrap();
+        assert_eq!(report.total_rejected(), 2);
+        assert!(report.examples.iter().all(|e| e.line == 3));
+
+        // Settings on the loader reach dictionaries loaded as a source
+        let loader =
+            Loader::nine_letter().with_hunspell(Hunspell::new().with_skip_forbidden(false));
+        let (words, _) = loader.read_from(&Source::hunspell(&dic)).unwrap();
+        assert!(words.contains(&"kindly".to_string()));
+        let (tree, _) = Hunspell::new()
+            .with_skip_forbidden(false)
+            .load(&dic, &aff, &Loader::nine_letter())
+            .unwrap();
+        assert!(tree.contains("kindly"));
+
+        fs::write(&aff, "SET KOI8-R\n").unwrap();
+        let error = Hunspell::new().read_words(&dic, &aff).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::Unsupported);
+
+        fs::remove_file(dic).unwrap();
+        fs::remove_file(aff).unwrap();
+    }
+}
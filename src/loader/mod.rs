@@ -0,0 +1,398 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Instant;
+
+use log::info;
+use rayon::prelude::*;
+
+use crate::word_tree::WordTree;
+use filter::Filter;
+use hunspell::Hunspell;
+use normalize::Normalizer;
+use progress::{NoProgress, Progress};
+use report::{LoadReport, DEFAULT_EXAMPLES};
+use source::Source;
+
+pub mod cache;
+pub mod filter;
+pub mod hunspell;
+pub mod normalize;
+pub mod progress;
+pub mod report;
+pub mod source;
+
+/// Reads word lists into a [`WordTree`]. Each line goes through the [`Normalizer`], and the words
+/// that come out are kept if the [`Filter`] accepts them.
+#[derive(Clone)]
+pub struct Loader {
+    normalizer: Normalizer,
+    filter: Filter,
+    hunspell: Hunspell,
+    progress: Arc<dyn Progress>,
+    max_examples: usize,
+}
+
+impl Default for Loader {
+    fn default() -> Self {
+        Loader {
+            normalizer: Normalizer::default(),
+            filter: Filter::default(),
+            hunspell: Hunspell::default(),
+            progress: Arc::new(NoProgress),
+            max_examples: DEFAULT_EXAMPLES,
+        }
+    }
+}
+
+impl fmt::Debug for Loader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Loader")
+            .field("normalizer", &self.normalizer)
+            .field("filter", &self.filter)
+            .field("hunspell", &self.hunspell)
+            .field("max_examples", &self.max_examples)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Loader {
+    /// Keeps every line as it is, without reporting progress.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Words for nine letter puzzles (see [`Filter::nine_letter`]), cleaned up with
+    /// [`Normalizer::standard`].
+    pub fn nine_letter() -> Self {
+        Self::new()
+            .with_normalizer(Normalizer::standard())
+            .with_filter(Filter::nine_letter())
+    }
+
+    pub fn with_normalizer(mut self, normalizer: Normalizer) -> Self {
+        self.normalizer = normalizer;
+        self
+    }
+
+    pub fn with_filter(mut self, filter: Filter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// How [`Source::Hunspell`] dictionaries are expanded, e.g. to keep forbidden words.
+    pub fn with_hunspell(mut self, hunspell: Hunspell) -> Self {
+        self.hunspell = hunspell;
+        self
+    }
+
+    /// Tells `progress` how many bytes have been checked while loading. Nothing is reported by
+    /// default, see [`progress::TerminalProgress`] for a progress bar.
+    pub fn with_progress<P: Progress + 'static>(mut self, progress: P) -> Self {
+        self.progress = Arc::new(progress);
+        self
+    }
+
+    /// How many rejected lines to keep in the [`LoadReport`].
+    pub fn with_max_examples(mut self, max_examples: usize) -> Self {
+        self.max_examples = max_examples;
+        self
+    }
+
+    pub fn normalizer(&self) -> &Normalizer {
+        &self.normalizer
+    }
+
+    pub fn filter(&self) -> &Filter {
+        &self.filter
+    }
+
+    /// Identifies the trees this loader builds, for [`cache::TreeCache`]: the crate version,
+    /// normalizer and [`Filter::cache_key`]. Custom predicates are only keyed by name, so two
+    /// loaders whose predicates share names but not behaviour share cached trees too.
+    pub fn cache_key(&self) -> String {
+        format!(
+            "{} {:?} {}",
+            env!("CARGO_PKG_VERSION"),
+            self.normalizer,
+            self.filter.cache_key()
+        )
+    }
+
+    /// The distinct normalized words from the file at `path` that pass the filter, in file order,
+    /// with a report of what happened to each line.
+    pub fn read_words<P: AsRef<Path>>(&self, path: P) -> io::Result<(Vec<String>, LoadReport)> {
+        self.read_from(&Source::file(path))
+    }
+
+    /// Like [`Self::read_words`], for any [`Source`].
+    pub fn read_from(&self, source: &Source) -> io::Result<(Vec<String>, LoadReport)> {
+        info!("Reading words from {}", source);
+        match source {
+            Source::File(path) => self.read_reader(BufReader::new(File::open(path)?)),
+            Source::Stdin => self.read_reader(io::stdin().lock()),
+            Source::Text(text) => Ok(self.read_str(text)),
+            Source::Hunspell { dic, aff } => {
+                let entries = self.hunspell.read_entries(dic, aff)?;
+                let text = entries
+                    .iter()
+                    .map(|(_, word)| word.as_str())
+                    .collect::<Vec<&str>>()
+                    .join("\n");
+                let (words, mut report) = self.read_str(&text);
+                // Point rejected words at the entry they came from, not at the expanded list
+                for example in &mut report.examples {
+                    example.line = entries[example.line - 1].0;
+                }
+                Ok((words, report))
+            }
+        }
+    }
+
+    /// Like [`Self::read_words`], reading until the end of `reader`.
+    pub fn read_reader<R: BufRead>(&self, mut reader: R) -> io::Result<(Vec<String>, LoadReport)> {
+        let mut text = String::new();
+        reader.read_to_string(&mut text)?;
+        Ok(self.read_str(&text))
+    }
+
+    /// Like [`Self::read_words`], for a word list already in memory.
+    pub fn read_str(&self, text: &str) -> (Vec<String>, LoadReport) {
+        let start_time = Instant::now();
+        self.progress.start(text.len() as u64);
+        let (lines, mut report) = self.process_lines(text);
+        self.progress.finish();
+
+        let mut seen = HashSet::new();
+        let mut words = Vec::new();
+        for line in lines {
+            let before = words.len();
+            words.extend(line.into_iter().filter(|w| seen.insert(w.clone())));
+            match words.len() > before {
+                true => report.accepted += 1,
+                false => report.duplicates += 1,
+            }
+        }
+
+        let duration = start_time.elapsed();
+        info!(
+            "Took {}.{:03}s to read {} words",
+            duration.as_secs(),
+            duration.subsec_millis(),
+            words.len()
+        );
+        info!("Loaded {}", report);
+        (words, report)
+    }
+
+    pub fn load<P: AsRef<Path>>(&self, path: P) -> io::Result<(WordTree, LoadReport)> {
+        self.load_from(&Source::file(path))
+    }
+
+    pub fn load_from(&self, source: &Source) -> io::Result<(WordTree, LoadReport)> {
+        let (words, report) = self.read_from(source)?;
+        Ok((build_tree(&words), report))
+    }
+
+    pub fn load_reader<R: BufRead>(&self, reader: R) -> io::Result<(WordTree, LoadReport)> {
+        let (words, report) = self.read_reader(reader)?;
+        Ok((build_tree(&words), report))
+    }
+
+    pub fn load_str(&self, text: &str) -> (WordTree, LoadReport) {
+        let (words, report) = self.read_str(text);
+        (build_tree(&words), report)
+    }
+
+    /// Normalizes and filters the lines of `file` in parallel, telling the progress reporter as
+    /// each one is done.
+    ///
+    /// Gives the words of each line that was not rejected, in file order, for the caller to
+    /// count as accepted or duplicate.
+    fn process_lines(&self, file: &str) -> (Vec<Vec<String>>, LoadReport) {
+        // Keep the line endings so that progress adds up to exactly the size of the file
+        let lines = file.split_inclusive('\n').collect::<Vec<&str>>();
+        lines
+            .par_iter()
+            .enumerate()
+            .map(|(i, raw)| {
+                self.progress.advance(raw.len() as u64);
+                let line = strip_line_ending(raw);
+                let mut report = LoadReport {
+                    lines: 1,
+                    ..Default::default()
+                };
+
+                let mut words = self.normalizer.normalize(line, &mut report.normalized);
+                let mut first_rejection = None;
+                words.retain(|word| match self.filter.check(word) {
+                    Ok(()) => true,
+                    Err(rejection) => {
+                        first_rejection.get_or_insert(rejection);
+                        false
+                    }
+                });
+                if words.is_empty() {
+                    report.reject(i + 1, line, first_rejection, self.max_examples);
+                    return (Vec::new(), report);
+                }
+                (vec![words], report)
+            })
+            .reduce(
+                || (Vec::new(), LoadReport::default()),
+                |(mut lines, mut report), (more_lines, more_report)| {
+                    lines.extend(more_lines);
+                    report.merge(more_report, self.max_examples);
+                    (lines, report)
+                },
+            )
+    }
+}
+
+/// `line` without its `\n` or `\r\n`, as [`str::lines`] would give it.
+fn strip_line_ending(line: &str) -> &str {
+    match line.strip_suffix('\n') {
+        Some(line) => line.strip_suffix('\r').unwrap_or(line),
+        None => line,
+    }
+}
+
+fn build_tree(words: &[String]) -> WordTree {
+    let start_time = Instant::now();
+    let tree = WordTree::new(words);
+    let duration = start_time.elapsed();
+    info!(
+        "Took {}.{:03}s to make tree",
+        duration.as_secs(),
+        duration.subsec_millis()
+    );
+    tree
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use crate::init_logger;
+
+    use super::filter::Rejection;
+    use super::normalize::NormalizeStats;
+    use super::progress::LogProgress;
+    use super::report::{Reason, RejectedLine};
+    use super::*;
+
+    #[test]
+    fn presets() {
+        init_logger();
+        let path = std::env::temp_dir().join(format!("nine_q_loader_{}.txt", std::process::id()));
+        fs::write(&path, "kin\nkind\n\nkinder\ncrêpe\nkindheartedness\n").unwrap();
+
+        let (all, report) = Loader::new().read_words(&path).unwrap();
+        assert_eq!(all, ["kin", "kind", "kinder", "crêpe", "kindheartedness"]);
+        assert_eq!(report.normalized, NormalizeStats::default());
+
+        let (nine_letter, report) = Loader::nine_letter().read_words(&path).unwrap();
+        assert_eq!(nine_letter, ["kind", "kinder", "crepe"]);
+        assert_eq!(report.normalized.diacritics_stripped, 1);
+
+        let custom = Loader::new()
+            .with_filter(Filter::new().with_predicate("starts with k", |w| w.starts_with('k')))
+            .load(&path)
+            .unwrap()
+            .0;
+        assert!(custom.contains("kindheartedness"));
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn sources() {
+        init_logger();
+        let loader = Loader::nine_letter();
+        let text = "kind\nkin\nkinder\n";
+
+        let (tree, report) = loader.load_str(text);
+        assert!(tree.contains("kinder"));
+        assert!(!tree.contains("kin"));
+        assert_eq!(report.lines, 3);
+
+        let (words, _) = loader.read_reader(io::Cursor::new(text)).unwrap();
+        assert_eq!(words, ["kind", "kinder"]);
+        let (words, _) = loader.read_from(&Source::text(text)).unwrap();
+        assert_eq!(words, ["kind", "kinder"]);
+
+        let error = loader
+            .read_reader(io::Cursor::new(b"kind\n\xff\n"))
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn report() {
+        init_logger();
+        let path = std::env::temp_dir().join(format!("nine_q_report_{}.txt", std::process::id()));
+        fs::write(
+            &path,
+            "kind\nkin\n\nKind\nnaïve\nkind-hearted\ndon't\nx1y2\n",
+        )
+        .unwrap();
+
+        let loader = Loader::new()
+            .with_normalizer(Normalizer::standard().with_punctuation(normalize::Punctuation::Split))
+            .with_filter(Filter::nine_letter())
+            .with_max_examples(3);
+        let (words, report) = loader.read_words(&path).unwrap();
+        fs::remove_file(path).unwrap();
+
+        assert_eq!(words, ["kind", "naive", "hearted"]);
+        assert_eq!(report.lines, 8);
+        // "kind-hearted" adds "hearted", so only "Kind" is a duplicate
+        assert_eq!(report.accepted, 3);
+        assert_eq!(report.duplicates, 1);
+        // Both halves of "don't" are too short, but the line is only rejected once
+        assert_eq!(report.rejected[&Reason::Length], 2);
+        assert_eq!(report.rejected[&Reason::Empty], 1);
+        assert_eq!(report.rejected[&Reason::Character], 1);
+        assert_eq!(
+            report.accepted + report.duplicates + report.total_rejected(),
+            report.lines
+        );
+        assert_eq!(
+            report.examples,
+            [
+                RejectedLine {
+                    line: 2,
+                    text: "kin".to_string(),
+                    rejection: Some(Rejection::Length(3)),
+                },
+                RejectedLine {
+                    line: 3,
+                    text: String::new(),
+                    rejection: None,
+                },
+                RejectedLine {
+                    line: 7,
+                    text: "don't".to_string(),
+                    rejection: Some(Rejection::Length(3)),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn progress_counts_bytes() {
+        init_logger();
+        let progress = Arc::new(LogProgress::new());
+        let loader = Loader::nine_letter().with_progress(progress.clone());
+
+        // Windows line endings, multi-byte characters and no newline at the end
+        let text = "kind\r\ncrêpe\n\nkinder";
+        let (words, report) = loader.read_str(text);
+        assert_eq!(words, ["kind", "crepe", "kinder"]);
+        assert_eq!(report.lines, 4);
+        assert_eq!(progress.done(), text.len() as u64);
+    }
+}
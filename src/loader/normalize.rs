@@ -0,0 +1,219 @@
+use std::fmt;
+use std::ops::AddAssign;
+
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
+/// What to do with punctuation inside a word, such as apostrophes and hyphens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Punctuation {
+    /// Leave it in, for the loader's filter to deal with.
+    #[default]
+    Keep,
+    /// Take it out, joining the pieces: `don't` becomes `dont`.
+    Drop,
+    /// Break the word apart there: `well-known` becomes `well` and `known`.
+    Split,
+}
+
+/// Cleans up lines of a word list before they are filtered, so that stray capitals, accents and
+/// whitespace do not cost a word its place in the tree.
+///
+/// Steps run in the order: trim, strip diacritics, lowercase, punctuation.
+#[derive(Debug, Clone, Default)]
+pub struct Normalizer {
+    trim: bool,
+    strip_diacritics: bool,
+    lowercase: bool,
+    punctuation: Punctuation,
+}
+
+/// How many lines each step of a [`Normalizer`] changed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NormalizeStats {
+    pub trimmed: usize,
+    pub diacritics_stripped: usize,
+    pub lowercased: usize,
+    pub punctuation_dropped: usize,
+    /// Lines broken into more than one word.
+    pub split: usize,
+    /// Lines with nothing left once normalized.
+    pub emptied: usize,
+}
+
+impl Normalizer {
+    /// Leaves lines as they are.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every step, dropping punctuation.
+    pub fn standard() -> Self {
+        Self::new()
+            .with_trim(true)
+            .with_strip_diacritics(true)
+            .with_lowercase(true)
+            .with_punctuation(Punctuation::Drop)
+    }
+
+    pub fn with_trim(mut self, trim: bool) -> Self {
+        self.trim = trim;
+        self
+    }
+
+    /// Decomposes letters (Unicode NFKD) and removes the accents, so `café` becomes `cafe`.
+    pub fn with_strip_diacritics(mut self, strip_diacritics: bool) -> Self {
+        self.strip_diacritics = strip_diacritics;
+        self
+    }
+
+    pub fn with_lowercase(mut self, lowercase: bool) -> Self {
+        self.lowercase = lowercase;
+        self
+    }
+
+    pub fn with_punctuation(mut self, punctuation: Punctuation) -> Self {
+        self.punctuation = punctuation;
+        self
+    }
+
+    /// The words in `line` once normalized, none if nothing is left of it.
+    pub fn normalize(&self, line: &str, stats: &mut NormalizeStats) -> Vec<String> {
+        let mut word = line.to_string();
+
+        if self.trim {
+            apply(&mut word, &mut stats.trimmed, |w| w.trim().to_string());
+        }
+        if self.strip_diacritics {
+            apply(&mut word, &mut stats.diacritics_stripped, |w| {
+                w.nfkd().filter(|c| !is_combining_mark(*c)).collect()
+            });
+        }
+        if self.lowercase {
+            apply(&mut word, &mut stats.lowercased, |w| w.to_lowercase());
+        }
+
+        let words = match self.punctuation {
+            Punctuation::Keep => vec![word],
+            Punctuation::Drop => {
+                apply(&mut word, &mut stats.punctuation_dropped, |w| {
+                    w.chars().filter(|c| !is_punctuation(*c)).collect()
+                });
+                vec![word]
+            }
+            Punctuation::Split => {
+                let words = word
+                    .split(is_punctuation)
+                    .filter(|w| !w.is_empty())
+                    .map(String::from)
+                    .collect::<Vec<String>>();
+                if words.len() > 1 {
+                    stats.split += 1;
+                }
+                words
+            }
+        };
+
+        let words = words
+            .into_iter()
+            .filter(|w| !w.is_empty())
+            .collect::<Vec<String>>();
+        if words.is_empty() && !line.is_empty() {
+            stats.emptied += 1;
+        }
+        words
+    }
+}
+
+/// Runs `step` on `word`, counting it if anything changed.
+fn apply(word: &mut String, count: &mut usize, step: impl Fn(&str) -> String) {
+    let next = step(word);
+    if next != *word {
+        *count += 1;
+        *word = next;
+    }
+}
+
+fn is_punctuation(c: char) -> bool {
+    c.is_ascii_punctuation()
+        || matches!(
+            c,
+            '\u{2018}' | '\u{2019}' | '\u{201c}' | '\u{201d}' | '\u{2010}'..='\u{2015}'
+        )
+}
+
+impl AddAssign for NormalizeStats {
+    fn add_assign(&mut self, other: Self) {
+        self.trimmed += other.trimmed;
+        self.diacritics_stripped += other.diacritics_stripped;
+        self.lowercased += other.lowercased;
+        self.punctuation_dropped += other.punctuation_dropped;
+        self.split += other.split;
+        self.emptied += other.emptied;
+    }
+}
+
+impl fmt::Display for NormalizeStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} trimmed, {} with diacritics stripped, {} lowercased, {} with punctuation dropped, \
+             {} split, {} emptied",
+            self.trimmed,
+            self.diacritics_stripped,
+            self.lowercased,
+            self.punctuation_dropped,
+            self.split,
+            self.emptied
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn normalize(normalizer: &Normalizer, lines: &[&str]) -> (Vec<String>, NormalizeStats) {
+        let mut stats = NormalizeStats::default();
+        let words = lines
+            .iter()
+            .flat_map(|l| normalizer.normalize(l, &mut stats))
+            .collect();
+        (words, stats)
+    }
+
+    #[test]
+    fn standard() {
+        let (words, stats) = normalize(
+            &Normalizer::standard(),
+            &["Kind ", "café", "don't", "Crème", "ﬁne", "'", "plain"],
+        );
+        assert_eq!(words, ["kind", "cafe", "dont", "creme", "fine", "plain"]);
+        assert_eq!(
+            stats,
+            NormalizeStats {
+                trimmed: 1,
+                diacritics_stripped: 3,
+                lowercased: 2,
+                punctuation_dropped: 2,
+                split: 0,
+                emptied: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn split() {
+        let normalizer = Normalizer::new().with_punctuation(Punctuation::Split);
+        let (words, stats) = normalize(&normalizer, &["well-known", "o’clock", "kind", "-"]);
+        assert_eq!(words, ["well", "known", "o", "clock", "kind"]);
+        assert_eq!(stats.split, 2);
+        assert_eq!(stats.emptied, 1);
+    }
+
+    #[test]
+    fn nothing_by_default() {
+        let (words, stats) = normalize(&Normalizer::new(), &[" Café-Crème "]);
+        assert_eq!(words, [" Café-Crème "]);
+        assert_eq!(stats, NormalizeStats::default());
+    }
+}
@@ -0,0 +1,189 @@
+use std::io::{self, Stderr};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use log::info;
+use pbr::{ProgressBar, Units};
+
+/// Hears how far through a word list the [`super::Loader`] is, in bytes.
+///
+/// Lines are checked in parallel, so `advance` can be called from several threads at once.
+pub trait Progress: Send + Sync {
+    /// Called before anything else, with the size of the word list.
+    fn start(&self, _total: u64) {}
+
+    /// Another `bytes` of the word list, line endings included, have been checked.
+    fn advance(&self, bytes: u64);
+
+    /// Called once every byte has been checked.
+    fn finish(&self) {}
+}
+
+/// Lets the caller keep a handle on a reporter after giving it to a loader.
+impl<P: Progress + ?Sized> Progress for Arc<P> {
+    fn start(&self, total: u64) {
+        (**self).start(total)
+    }
+
+    fn advance(&self, bytes: u64) {
+        (**self).advance(bytes)
+    }
+
+    fn finish(&self) {
+        (**self).finish()
+    }
+}
+
+/// Reports nothing, for loading inside a library or server.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoProgress;
+
+impl Progress for NoProgress {
+    fn advance(&self, _bytes: u64) {}
+}
+
+/// Draws a progress bar on stderr, keeping stdout clean for the results.
+///
+/// Bytes are counted without locking, and the bar is only redrawn when the percentage changes,
+/// so lines checked in parallel do not wait on each other.
+#[derive(Default)]
+pub struct TerminalProgress {
+    bar: Mutex<Option<ProgressBar<Stderr>>>,
+    total: AtomicU64,
+    done: AtomicU64,
+}
+
+impl TerminalProgress {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Progress for TerminalProgress {
+    fn start(&self, total: u64) {
+        let mut bar = ProgressBar::on(io::stderr(), total);
+        bar.set_units(Units::Bytes);
+        self.total.store(total, Ordering::Relaxed);
+        self.done.store(0, Ordering::Relaxed);
+        *self.bar.lock().unwrap() = Some(bar);
+    }
+
+    fn advance(&self, bytes: u64) {
+        let total = self.total.load(Ordering::Relaxed).max(1);
+        let before = self.done.fetch_add(bytes, Ordering::Relaxed);
+        let after = before + bytes;
+        if before * 100 / total == after * 100 / total {
+            return;
+        }
+        // Read the count again under the lock, so a slower thread never moves the bar back
+        if let Some(bar) = self.bar.lock().unwrap().as_mut() {
+            bar.set(self.done.load(Ordering::Relaxed));
+        }
+    }
+
+    fn finish(&self) {
+        if let Some(bar) = self.bar.lock().unwrap().as_mut() {
+            bar.set(self.done.load(Ordering::Relaxed));
+            bar.finish_print("Finished loading words");
+        }
+    }
+}
+
+/// Logs progress at info level every `step` percent, for when nobody is watching a terminal.
+#[derive(Debug)]
+pub struct LogProgress {
+    step: u64,
+    total: AtomicU64,
+    done: AtomicU64,
+}
+
+impl Default for LogProgress {
+    fn default() -> Self {
+        LogProgress {
+            step: 10,
+            total: AtomicU64::new(0),
+            done: AtomicU64::new(0),
+        }
+    }
+}
+
+impl LogProgress {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Logs every `step` percent, which must be between 1 and 100.
+    pub fn with_step(mut self, step: u64) -> Self {
+        self.step = step.clamp(1, 100);
+        self
+    }
+
+    /// Bytes checked so far.
+    pub fn done(&self) -> u64 {
+        self.done.load(Ordering::Relaxed)
+    }
+}
+
+impl Progress for LogProgress {
+    fn start(&self, total: u64) {
+        self.total.store(total, Ordering::Relaxed);
+        self.done.store(0, Ordering::Relaxed);
+    }
+
+    fn advance(&self, bytes: u64) {
+        let total = self.total.load(Ordering::Relaxed).max(1);
+        let before = self.done.fetch_add(bytes, Ordering::Relaxed);
+        let after = before + bytes;
+        let percent = |done: u64| done * 100 / total / self.step * self.step;
+        if percent(before) != percent(after) {
+            info!(
+                "Loaded {}% of the word list ({} of {} bytes)",
+                percent(after),
+                after,
+                total
+            );
+        }
+    }
+
+    fn finish(&self) {
+        info!("Finished loading {} bytes", self.done());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::init_logger;
+
+    use super::*;
+
+    #[test]
+    fn log_progress() {
+        init_logger();
+        let progress = LogProgress::new().with_step(25);
+        progress.start(200);
+        for _ in 0..20 {
+            progress.advance(10);
+        }
+        progress.finish();
+        assert_eq!(progress.done(), 200);
+    }
+
+    #[test]
+    fn terminal_progress() {
+        let progress = TerminalProgress::new();
+        progress.start(1000);
+        for _ in 0..1000 {
+            progress.advance(1);
+        }
+        progress.finish();
+        assert_eq!(progress.done.load(Ordering::Relaxed), 1000);
+    }
+
+    #[test]
+    fn terminal_progress_without_start() {
+        // Advancing before starting is ignored rather than panicking
+        let progress = TerminalProgress::new();
+        progress.advance(10);
+        progress.finish();
+    }
+}
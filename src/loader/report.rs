@@ -0,0 +1,177 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use super::filter::Rejection;
+use super::normalize::NormalizeStats;
+
+/// How many rejected lines a [`LoadReport`] keeps by default.
+pub const DEFAULT_EXAMPLES: usize = 10;
+
+/// What kind of problem kept a line out of the tree, for counting rejections.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Reason {
+    /// Nothing was left of the line once normalized, e.g. a blank line.
+    Empty,
+    Length,
+    Character,
+    /// A custom predicate, by name.
+    Predicate(String),
+}
+
+/// A line that did not make it into the tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RejectedLine {
+    /// Counting from 1, as editors do. For a Hunspell dictionary this is the `.dic` line the
+    /// rejected word was expanded from, and `text` is the expanded word.
+    pub line: usize,
+    pub text: String,
+    /// `None` if the line was [`Reason::Empty`].
+    pub rejection: Option<Rejection>,
+}
+
+/// What happened to every line of a word list while loading it.
+///
+/// Each line is counted exactly once, as accepted, a duplicate or rejected, so those add up to
+/// `lines`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LoadReport {
+    pub lines: usize,
+    /// Lines that added at least one new word.
+    pub accepted: usize,
+    /// Lines whose words had all been added by earlier lines.
+    pub duplicates: usize,
+    /// Lines with no words that passed the filter, by the reason the first word was rejected.
+    pub rejected: BTreeMap<Reason, usize>,
+    /// The first few rejected lines, in file order.
+    pub examples: Vec<RejectedLine>,
+    pub normalized: NormalizeStats,
+}
+
+impl From<&Rejection> for Reason {
+    fn from(rejection: &Rejection) -> Self {
+        match rejection {
+            Rejection::Length(_) => Reason::Length,
+            Rejection::Character(_) => Reason::Character,
+            Rejection::Predicate(name) => Reason::Predicate(name.clone()),
+        }
+    }
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Reason::Empty => write!(f, "empty"),
+            Reason::Length => write!(f, "length"),
+            Reason::Character => write!(f, "characters"),
+            Reason::Predicate(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+impl LoadReport {
+    pub fn total_rejected(&self) -> usize {
+        self.rejected.values().sum()
+    }
+
+    /// Counts a rejected line, keeping it as an example if there are fewer than `max_examples`.
+    pub(super) fn reject(
+        &mut self,
+        line: usize,
+        text: &str,
+        rejection: Option<Rejection>,
+        max_examples: usize,
+    ) {
+        let reason = rejection.as_ref().map_or(Reason::Empty, Reason::from);
+        *self.rejected.entry(reason).or_default() += 1;
+        if self.examples.len() < max_examples {
+            self.examples.push(RejectedLine {
+                line,
+                text: text.to_string(),
+                rejection,
+            });
+        }
+    }
+
+    /// Adds the counts from the lines after the ones in `self`.
+    pub(super) fn merge(&mut self, later: LoadReport, max_examples: usize) {
+        self.lines += later.lines;
+        self.accepted += later.accepted;
+        self.duplicates += later.duplicates;
+        for (reason, count) in later.rejected {
+            *self.rejected.entry(reason).or_default() += count;
+        }
+        let room = max_examples.saturating_sub(self.examples.len());
+        self.examples.extend(later.examples.into_iter().take(room));
+        self.normalized += later.normalized;
+    }
+}
+
+impl fmt::Display for LoadReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} lines, {} accepted, {} duplicates, {} rejected",
+            self.lines,
+            self.accepted,
+            self.duplicates,
+            self.total_rejected()
+        )?;
+        if !self.rejected.is_empty() {
+            let reasons = self
+                .rejected
+                .iter()
+                .map(|(reason, count)| format!("{} {}", count, reason))
+                .collect::<Vec<String>>();
+            write!(f, " ({})", reasons.join(", "))?;
+        }
+        write!(f, "\nNormalized: {}", self.normalized)?;
+        for example in &self.examples {
+            write!(f, "\n  line {}: {:?}", example.line, example.text)?;
+            if let Some(rejection) = &example.rejection {
+                write!(f, " {}", rejection)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn merge_keeps_the_first_examples() {
+        let mut first = LoadReport {
+            lines: 2,
+            ..Default::default()
+        };
+        first.reject(1, "", None, 2);
+        let mut second = LoadReport {
+            lines: 3,
+            ..Default::default()
+        };
+        second.reject(3, "kin", Some(Rejection::Length(3)), 2);
+        second.reject(4, "ki", Some(Rejection::Length(2)), 2);
+
+        first.merge(second, 2);
+        assert_eq!(first.lines, 5);
+        assert_eq!(first.rejected[&Reason::Length], 2);
+        assert_eq!(first.total_rejected(), 3);
+        assert_eq!(
+            first
+                .examples
+                .iter()
+                .map(|e| e.line)
+                .collect::<Vec<usize>>(),
+            [1, 3]
+        );
+        assert_eq!(
+            first.to_string(),
+            "5 lines, 0 accepted, 0 duplicates, 3 rejected (1 empty, 2 length)\n\
+             Normalized: 0 trimmed, 0 with diacritics stripped, 0 lowercased, 0 with punctuation \
+             dropped, 0 split, 0 emptied\n  \
+             line 1: \"\"\n  \
+             line 3: \"kin\" 3 characters long"
+        );
+    }
+}
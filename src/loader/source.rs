@@ -0,0 +1,82 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Where a word list comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    File(PathBuf),
+    Stdin,
+    /// A word list already in memory, one word per line.
+    Text(String),
+    /// A Hunspell `.dic` file and its `.aff` rules, expanded into every word they make before
+    /// loading.
+    Hunspell {
+        dic: PathBuf,
+        aff: PathBuf,
+    },
+}
+
+impl Source {
+    pub fn file<P: AsRef<Path>>(path: P) -> Self {
+        Source::File(path.as_ref().to_path_buf())
+    }
+
+    pub fn text(text: &str) -> Self {
+        Source::Text(text.to_string())
+    }
+
+    /// The Hunspell dictionary at `dic`, with the `.aff` file of the same name.
+    pub fn hunspell<P: AsRef<Path>>(dic: P) -> Self {
+        let dic = dic.as_ref().to_path_buf();
+        Source::Hunspell {
+            aff: dic.with_extension("aff"),
+            dic,
+        }
+    }
+
+    /// Reads a command line argument the usual way: `-` is stdin, anything else a file. Files
+    /// ending in `.dic` are Hunspell dictionaries.
+    pub fn from_arg<P: AsRef<Path>>(arg: P) -> Self {
+        let path = arg.as_ref();
+        if path == Path::new("-") {
+            Source::Stdin
+        } else if path.extension().is_some_and(|e| e == "dic") {
+            Source::hunspell(path)
+        } else {
+            Source::file(path)
+        }
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::File(path) => write!(f, "{}", path.display()),
+            Source::Stdin => write!(f, "stdin"),
+            Source::Text(text) => write!(f, "{} bytes of text", text.len()),
+            Source::Hunspell { dic, aff } => write!(f, "{} with {}", dic.display(), aff.display()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn from_arg() {
+        assert_eq!(Source::from_arg("-"), Source::Stdin);
+        assert_eq!(
+            Source::from_arg("words_eng.txt"),
+            Source::File(PathBuf::from("words_eng.txt"))
+        );
+        assert_eq!(Source::from_arg("./-").to_string(), "./-");
+        assert_eq!(
+            Source::from_arg("en_GB.dic"),
+            Source::Hunspell {
+                dic: PathBuf::from("en_GB.dic"),
+                aff: PathBuf::from("en_GB.aff"),
+            }
+        );
+    }
+}
@@ -1,30 +1,222 @@
 use std::{
-    io::{self, Error},
+    fs::read_to_string,
+    io::{self, IsTerminal},
     path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
-use clap::{Parser, ValueEnum};
-use nine_q_lib::{game::NineP, load_9p_like_words};
+use clap::{error::ErrorKind, Args, CommandFactory, Parser, Subcommand, ValueEnum};
+use nine_q_lib::{
+    dictionary::{
+        blocklist::{Blocklist, Category},
+        Dictionary, LayerKind,
+    },
+    game::{
+        daily::Date,
+        difficulty::difficulty,
+        generator::{count_reasons, Constraints, GenerateError, Generator, Rejected},
+        session::GameSession,
+        NineP,
+    },
+    loader::{
+        cache::{CacheStatus, TreeCache},
+        hunspell::Hunspell,
+        progress::{LogProgress, TerminalProgress},
+        source::Source,
+        Loader,
+    },
+    word_tree::WordTree,
+};
 use rayon::slice::ParallelSliceMut;
 
+mod play;
+
 #[derive(Parser)]
-#[command(version, about, long_about = None)]
+#[command(version, about, long_about = None, subcommand_negates_reqs = true)]
 struct Cli {
     /// Text file containing newline seperated values for all valid words in the game. e.g.
-    /// `words_eng.txt`
-    #[arg(short, long, value_name = "FILE", default_value = "words_eng.txt")]
+    /// `words_eng.txt`, or `-` to read the list from stdin. A Hunspell `.dic` file is expanded
+    /// using the `.aff` file of the same name
+    #[arg(
+        short,
+        long,
+        value_name = "FILE",
+        default_value = "words_eng.txt",
+        global = true
+    )]
     word_list: PathBuf,
 
+    /// Extra words to add to the word list, one per line or as a Hunspell `.dic` file. Can be
+    /// given more than once
+    #[arg(long = "add", value_name = "FILE", global = true)]
+    additions: Vec<PathBuf>,
+
+    /// Words to take out of the word list, one per line or as a Hunspell `.dic` file. Applied
+    /// after `--add`, and can be given more than once
+    #[arg(long = "remove", value_name = "FILE", global = true)]
+    removals: Vec<PathBuf>,
+
+    /// Leave out offensive words in this category (vulgar, slur or medical) using the built-in
+    /// blocklist. Can be given more than once
+    #[arg(long, value_name = "CATEGORY", global = true)]
+    block: Vec<Category>,
+
+    /// Keep slurs in the boards made by `generate`, `daily` and `play`, which leave them out by
+    /// default
+    #[arg(long, global = true)]
+    allow_slurs: bool,
+
+    /// Keep the words a Hunspell `.dic` file marks as forbidden
+    #[arg(long, global = true)]
+    keep_forbidden: bool,
+
+    /// Keep the words a Hunspell `.dic` file only allows inside compound words
+    #[arg(long, global = true)]
+    keep_compound_only: bool,
+
+    /// Print what happened to the lines of the word list while loading it, including the first
+    /// few lines that were left out and why
+    #[arg(long, global = true)]
+    load_report: bool,
+
+    /// Always build the word tree from the word list, without reading or writing the cache
+    #[arg(long, global = true, conflicts_with = "rebuild_cache")]
+    no_cache: bool,
+
+    /// Build the word tree from the word list even if it is cached, and cache the new tree
+    #[arg(long, global = true)]
+    rebuild_cache: bool,
+
+    #[arg(short, long, action = clap::ArgAction::Count, value_parser = clap::value_parser!(u8).range(0..6), default_value_t = 0_u8, global = true)]
+    verbosity: u8,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    solve: Option<SolveArgs>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print every word that can be made from a board (the default)
+    Solve(SolveArgs),
+    /// Generate a new board from the word list
+    Generate(GenerateArgs),
+    /// Play a board interactively
+    Play(PlayArgs),
+    /// Print the board of the day, which is the same for everyone using the same word list
+    Daily {
+        /// Day to print the board for, as YYYY-MM-DD. Defaults to today (UTC)
+        #[arg(short, long)]
+        date: Option<Date>,
+    },
+}
+
+#[derive(Args)]
+struct PlayArgs {
+    /// Letters to play with, a new board is generated if not given
+    #[arg(short, long, value_name = "LETTERS", conflicts_with_all = ["seed", "date"])]
+    board: Option<String>,
+
+    /// Letter that every word must use, when giving a `--board`
+    #[arg(short, long, value_name = "LETTER", requires = "board")]
+    centre: Option<char>,
+
+    /// Seed for a generated board
+    #[arg(short, long, conflicts_with = "date")]
+    seed: Option<u64>,
+
+    /// Play the daily board for this day (YYYY-MM-DD)
+    #[arg(short, long)]
+    date: Option<Date>,
+
+    /// Carry on a saved game
+    #[arg(short, long, value_name = "FILE", conflicts_with_all = ["board", "seed", "date"])]
+    resume: Option<PathBuf>,
+
+    /// Time limit in seconds, guesses after the time runs out are not counted
+    #[arg(short, long, value_name = "SECONDS")]
+    time: Option<u64>,
+
+    /// Where to save the game when quitting (defaults to the `--resume` file)
+    #[arg(long, value_name = "FILE")]
+    save: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct GenerateArgs {
+    /// Seed for the board, the same seed and word list always give the same board. Picked at
+    /// random if not given
+    #[arg(short, long)]
+    seed: Option<u64>,
+
+    /// Only accept boards with exactly one nine letter word
+    #[arg(long)]
+    single: bool,
+
+    /// Fewest words a board may have
+    #[arg(long, value_name = "COUNT")]
+    min_words: Option<usize>,
+
+    /// Most words a board may have
+    #[arg(long, value_name = "COUNT")]
+    max_words: Option<usize>,
+
+    /// Newline separated words that must not be solutions
+    #[arg(long, value_name = "FILE")]
+    blocklist: Option<PathBuf>,
+
+    /// Newline separated list of common words, used with `--min-common`
+    #[arg(long, value_name = "FILE", requires = "min_common")]
+    common: Option<PathBuf>,
+
+    /// Fewest solutions that must be in the `--common` list
+    #[arg(long, value_name = "COUNT", requires = "common")]
+    min_common: Option<usize>,
+
+    /// How many seeds to try before giving up
+    #[arg(
+        long,
+        value_name = "COUNT",
+        default_value_t = 1000,
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..)
+    )]
+    attempts: usize,
+}
+
+#[derive(Args)]
+struct SolveArgs {
     /// The available letters to play with. e.g. `abcdefghi`
     #[arg(short, long, value_name = "LETTERS")]
     board: String,
 
+    /// Letter that every word must use
+    #[arg(short, long, value_name = "LETTER")]
+    centre: Option<char>,
+
     /// Sorting method to use when displaying results
     #[arg(value_enum, default_value_t = Sorting::Alpha)]
     sorting: Sorting,
 
-    #[arg(short, long, action = clap::ArgAction::Count, value_parser = clap::value_parser!(u8).range(0..6), default_value_t = 0_u8)]
-    verbosity: u8,
+    /// Print hints (word counts by letter and length, two-letter prefixes) instead of the words
+    #[arg(long)]
+    hints: bool,
+}
+
+impl Cli {
+    /// Global options can come before a command, but the top level board only makes sense
+    /// without one.
+    fn check(self) -> Result<Self, clap::Error> {
+        if self.command.is_some() && self.solve.is_some() {
+            return Err(Cli::command().error(
+                ErrorKind::ArgumentConflict,
+                "`--board` and the sorting can only be given before a command when solving \
+                 without one, give them after the command instead",
+            ));
+        }
+        Ok(self)
+    }
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
@@ -40,7 +232,7 @@ enum Sorting {
 }
 
 fn main() -> std::io::Result<()> {
-    let cli = Cli::parse();
+    let cli = Cli::parse().check().unwrap_or_else(|e| e.exit());
     // Set verbosity level
     let level = match cli.verbosity {
         0 => log::LevelFilter::Off,
@@ -68,12 +260,134 @@ fn main() -> std::io::Result<()> {
         ));
     }
 
-    let word_tree = load_9p_like_words(word_list.to_str().unwrap())?;
-    let board = NineP::new(cli.board, word_tree);
+    // Stdin can only be read to the end once, by one list or by the guesses when playing
+    let from_stdin = std::iter::once(word_list.as_os_str())
+        .chain(cli.additions.iter().map(|p| p.as_os_str()))
+        .chain(cli.removals.iter().map(|p| p.as_os_str()))
+        .filter(|arg| matches!(Source::from_arg(arg), Source::Stdin))
+        .count();
+    if from_stdin > 1 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Only one word list can be read from `-`",
+        ));
+    }
+    if from_stdin > 0 && matches!(cli.command, Some(Command::Play(_))) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "`play` reads guesses from stdin, so word lists can not be read from `-`",
+        ));
+    }
+
+    // Keep the bar off stdout, and fall back to the log when stderr is not a terminal either
+    let loader = Loader::nine_letter().with_hunspell(
+        Hunspell::new()
+            .with_skip_forbidden(!cli.keep_forbidden)
+            .with_skip_only_in_compound(!cli.keep_compound_only),
+    );
+    let loader = match io::stderr().is_terminal() {
+        true => loader.with_progress(TerminalProgress::new()),
+        false => loader.with_progress(LogProgress::new()),
+    };
+
+    let mut word_tree = if cli.additions.is_empty() && cli.removals.is_empty() {
+        match Source::from_arg(&word_list) {
+            // Only files can be cached, there is nothing to check stdin against next time
+            Source::File(path) if !cli.no_cache => {
+                let cache =
+                    TreeCache::new(TreeCache::default_dir()).with_rebuild(cli.rebuild_cache);
+                let (tree, status) = cache.load(path, "nine_letter", &loader)?;
+                if cli.load_report {
+                    match status {
+                        CacheStatus::Built(report) => eprintln!("{}", report),
+                        _ => eprintln!(
+                            "Loaded a cached tree from {}, there is no load report unless the \
+                             tree is built again (see --rebuild-cache)",
+                            cache.dir().display()
+                        ),
+                    }
+                }
+                tree
+            }
+            source => {
+                let (tree, report) = loader.load_from(&source)?;
+                if cli.load_report {
+                    eprintln!("{}", report);
+                }
+                tree
+            }
+        }
+    } else {
+        let mut dictionary =
+            Dictionary::new().with_layer(LayerKind::Base, Source::from_arg(&word_list));
+        for path in cli.additions {
+            dictionary = dictionary.with_layer(LayerKind::Additions, Source::from_arg(path));
+        }
+        for path in cli.removals {
+            dictionary = dictionary.with_layer(LayerKind::Removals, Source::from_arg(path));
+        }
+        let merged = dictionary.load_with(&loader)?;
+        if cli.load_report {
+            for (layer, report) in merged.reports() {
+                eprintln!("{:?} {}: {}", layer.kind, layer.source, report);
+            }
+        }
+        merged.into_tree()
+    };
+
+    // Blocked after loading so that cached trees can be shared between commands
+    let mut block = cli.block;
+    let for_players = matches!(
+        cli.command,
+        Some(Command::Generate(_) | Command::Play(_) | Command::Daily { .. })
+    );
+    if for_players && !cli.allow_slurs && !block.contains(&Category::Slur) {
+        block.push(Category::Slur);
+    }
+    if !block.is_empty() {
+        let blocked = Blocklist::built_in()
+            .with_categories(&block)
+            .remove_from(&mut word_tree);
+        log::info!("Blocked {}", blocked);
+        if cli.load_report {
+            eprintln!("Blocked {}", blocked);
+        }
+    }
+
+    match cli.command {
+        Some(Command::Solve(args)) => solve(args, word_tree),
+        Some(Command::Generate(args)) => generate(args, word_tree),
+        Some(Command::Play(args)) => play(args, word_tree),
+        Some(Command::Daily { date }) => daily(date.unwrap_or_else(Date::today), word_tree),
+        None => match cli.solve {
+            Some(args) => solve(args, word_tree),
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Expected a board to solve or a command, see `--help`",
+            )),
+        },
+    }
+}
+
+fn solve(args: SolveArgs, word_tree: WordTree) -> io::Result<()> {
+    let mut board = NineP::new(args.board, word_tree);
+    if let Some(centre) = args.centre {
+        board = board.with_centre(centre).map_err(invalid_input)?;
+    }
+
+    if args.hints {
+        let hints = board.hints();
+        println!("{} words to find\n", hints.words_left());
+        println!("{}\n", hints.grid());
+        println!("{}", hints.prefix_counts());
+        return Ok(());
+    }
+
+    println!("{}\n", board.render(io::stdout().is_terminal()));
 
     let mut lines = board.solve();
 
-    match cli.sorting {
+    match args.sorting {
         Sorting::RevAlpha => lines.reverse(),
         Sorting::Length => lines.par_sort_unstable_by(|a, b| a.len().cmp(&b.len())),
         Sorting::RevLength => lines.par_sort_unstable_by(|a, b| b.len().cmp(&a.len())),
@@ -86,3 +400,179 @@ fn main() -> std::io::Result<()> {
     }
     Ok(())
 }
+
+fn generate(args: GenerateArgs, word_tree: WordTree) -> io::Result<()> {
+    let seed = args.seed.unwrap_or_else(time_seed);
+
+    let mut constraints = Constraints::new();
+    if args.single {
+        constraints = constraints.single_full_word();
+    }
+    if args.min_words.is_some() || args.max_words.is_some() {
+        constraints = constraints
+            .word_count(args.min_words.unwrap_or(0)..=args.max_words.unwrap_or(usize::MAX));
+    }
+    if let Some(path) = args.blocklist {
+        constraints = constraints.blocklist(read_word_list(path)?);
+    }
+    if let (Some(path), Some(count)) = (args.common, args.min_common) {
+        constraints = constraints.min_common_words(count, read_word_list(path)?);
+    }
+
+    let generated = match Generator::new(word_tree).generate_with(seed, &constraints, args.attempts)
+    {
+        Ok(generated) => generated,
+        Err(GenerateError::Rejected(rejected)) => {
+            print_rejections(&rejected);
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("No board met the constraints in {} attempts", args.attempts),
+            ));
+        }
+        Err(GenerateError::NoWords) => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "The word list has no nine letter words to build a board from",
+            ))
+        }
+        // Ruled out by the range on `--attempts`
+        Err(e @ GenerateError::NoAttempts) => return Err(invalid_input(e)),
+    };
+
+    print_rejections(&generated.rejected);
+
+    print_board(&generated.puzzle);
+    println!("Seed:   {}", generated.seed);
+    Ok(())
+}
+
+/// Counts the rejected boards by reason, the full list is logged at debug level.
+fn print_rejections(rejected: &[Rejected]) {
+    if rejected.is_empty() {
+        return;
+    }
+    let reasons = count_reasons(rejected)
+        .into_iter()
+        .map(|(reason, count)| format!("{} {}", count, reason))
+        .collect::<Vec<String>>();
+    eprintln!("Rejected {} boards: {}", rejected.len(), reasons.join(", "));
+}
+
+fn daily(date: Date, word_tree: WordTree) -> io::Result<()> {
+    let Some(board) = Generator::new(word_tree).daily(date) else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "The word list has no nine letter words to build a board from",
+        ));
+    };
+
+    println!("Date:   {}", date);
+    print_board(&board);
+    Ok(())
+}
+
+fn print_board(board: &NineP) {
+    println!("{}", board.render(io::stdout().is_terminal()));
+    println!("Board:  {}", board.letters());
+    if let Some(centre) = board.centre() {
+        println!("Centre: {}", centre);
+    }
+    println!("Difficulty: {}", difficulty(board));
+}
+
+fn play(args: PlayArgs, word_tree: WordTree) -> io::Result<()> {
+    let session = if let Some(path) = &args.resume {
+        let resumed = GameSession::load(path, word_tree)?;
+        if resumed.dictionary_changed {
+            eprintln!("Warning: the word list has changed since this game was saved");
+        }
+        if !resumed.dropped_words.is_empty() {
+            eprintln!(
+                "These words are no longer accepted and were removed: {}",
+                resumed.dropped_words.join(", ")
+            );
+        }
+        resumed.session
+    } else if let Some(letters) = args.board {
+        let mut board = NineP::new(letters, word_tree);
+        if let Some(centre) = args.centre {
+            board = board.with_centre(centre).map_err(invalid_input)?;
+        }
+        GameSession::new(board)
+    } else {
+        let generator = Generator::new(word_tree);
+        let board = match args.date {
+            Some(date) => generator.daily(date),
+            None => generator.generate(args.seed.unwrap_or_else(time_seed)),
+        };
+        let Some(board) = board else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "The word list has no nine letter words to build a board from",
+            ));
+        };
+        GameSession::new(board)
+    };
+    let session = match args.time {
+        Some(seconds) => session.with_time_limit(Duration::from_secs(seconds)),
+        None => session,
+    };
+
+    play::play(
+        session,
+        args.save.or(args.resume),
+        io::stdin().lock(),
+        io::stdout().lock(),
+        io::stdout().is_terminal(),
+    )
+}
+
+fn invalid_input<E: ToString>(error: E) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, error.to_string())
+}
+
+fn time_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or_default()
+}
+
+fn read_word_list(path: PathBuf) -> io::Result<Vec<String>> {
+    Ok(read_to_string(path)?
+        .lines()
+        .map(|l| l.trim().to_lowercase())
+        .filter(|l| !l.is_empty())
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn global_options_before_commands() {
+        let parse = |args: &[&str]| Cli::try_parse_from(args).and_then(Cli::check);
+        let cli = parse(&["nine_q", "-w", "x", "generate"]).unwrap();
+        assert!(matches!(cli.command, Some(Command::Generate(_))));
+        assert_eq!(cli.word_list, PathBuf::from("x"));
+
+        let cli = parse(&["nine_q", "--no-cache", "solve", "-b", "abc"]).unwrap();
+        assert!(cli.no_cache);
+        assert!(matches!(cli.command, Some(Command::Solve(_))));
+
+        let cli = parse(&["nine_q", "--block", "vulgar", "play"]).unwrap();
+        assert_eq!(cli.block, [Category::Vulgar]);
+        assert!(matches!(cli.command, Some(Command::Play(_))));
+
+        // Solving without a command still works, with the sorting as a positional
+        let cli = parse(&["nine_q", "-b", "abc", "length"]).unwrap();
+        assert!(cli.command.is_none());
+        assert_eq!(cli.solve.unwrap().sorting, Sorting::Length);
+
+        let error = parse(&["nine_q", "-b", "abc", "generate"])
+            .map(|_| ())
+            .unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::ArgumentConflict);
+    }
+}
@@ -0,0 +1,300 @@
+use std::{
+    io::{self, BufRead, Write},
+    path::PathBuf,
+    time::Duration,
+};
+
+use nine_q_lib::game::{
+    session::{GameSession, Guess, Stopped},
+    NineP,
+};
+
+/// How many of the best missed words to show when the game ends.
+const BEST_MISSED: usize = 5;
+
+const PAUSED: &str = "The game is paused, :resume to carry on";
+
+const HELP: &str = "Type a word to guess it, or one of these commands:
+  :shuffle      rearrange the letters
+  :hint         show how many words are left, by first letter and length
+  :reveal [N]   reveal another letter of the Nth word still to find (default 1)
+  :score        show your score, rating and time left
+  :pause        stop the clock in a timed game
+  :resume       restart the clock
+  :found        list the words you have found
+  :save [FILE]  save the game to carry on later
+  :giveup       show the words you missed and end the game
+  :quit         end the game
+  :help         show this message";
+
+/// Runs the interactive game until the player quits, gives up or input runs out.
+pub fn play<R: BufRead, W: Write>(
+    mut session: GameSession,
+    mut save_path: Option<PathBuf>,
+    input: R,
+    mut output: W,
+    highlight: bool,
+) -> io::Result<()> {
+    writeln!(output, "{}\n", HELP)?;
+    print_board(&mut output, session.puzzle(), highlight)?;
+    writeln!(output, "{} words to find", session.total_words())?;
+    if let Some(timer) = session.timer() {
+        writeln!(output, "{} to find them", format_time(timer.remaining()))?;
+    }
+    session.resume();
+
+    let mut lines = input.lines();
+    loop {
+        write!(output, "> ")?;
+        output.flush()?;
+        let Some(line) = lines.next().transpose()? else {
+            break;
+        };
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        // Running out of time ends the game, whatever was typed after the clock ran out
+        if session.is_time_up() {
+            return time_up(&mut output, &session);
+        }
+
+        let mut words = line.split_whitespace();
+        match words.next().unwrap_or_default() {
+            ":shuffle" => {
+                session.shuffle(crate::time_seed());
+                print_board(&mut output, session.puzzle(), highlight)?;
+            }
+            ":hint" => match session.hints() {
+                Ok(hints) => {
+                    writeln!(output, "{} words left\n", hints.words_left())?;
+                    writeln!(output, "{}\n", hints.grid())?;
+                    writeln!(output, "{}", hints.prefix_counts())?;
+                }
+                Err(Stopped::Paused) => writeln!(output, "{}", PAUSED)?,
+                Err(Stopped::TimeUp) => return time_up(&mut output, &session),
+            },
+            ":reveal" => {
+                let index = words
+                    .next()
+                    .and_then(|n| n.parse::<usize>().ok())
+                    .unwrap_or(1);
+                match session.reveal_next_letter(index.saturating_sub(1)) {
+                    Ok(Some(letters)) => {
+                        writeln!(output, "Word {} starts with '{}'", index, letters)?
+                    }
+                    Ok(None) => writeln!(output, "There is no word {} left to find", index)?,
+                    Err(Stopped::Paused) => writeln!(output, "{}", PAUSED)?,
+                    Err(Stopped::TimeUp) => return time_up(&mut output, &session),
+                }
+            }
+            ":score" => print_score(&mut output, &session)?,
+            ":pause" => {
+                session.pause();
+                writeln!(output, "Paused, :resume to carry on")?;
+            }
+            ":resume" => {
+                session.resume();
+                print_board(&mut output, session.puzzle(), highlight)?;
+            }
+            ":found" => match session.stopped() {
+                None => {
+                    let mut found = session.found_words().to_vec();
+                    found.sort();
+                    writeln!(output, "{}", found.join(" "))?;
+                }
+                Some(Stopped::Paused) => writeln!(output, "{}", PAUSED)?,
+                Some(Stopped::TimeUp) => return time_up(&mut output, &session),
+            },
+            ":save" => {
+                match words.next().map(PathBuf::from).or(save_path.clone()) {
+                    // Keep playing if the save fails, rather than losing the game
+                    Some(path) => match session.save(&path) {
+                        Ok(()) => {
+                            writeln!(output, "Saved to {}", path.display())?;
+                            save_path = Some(path);
+                        }
+                        Err(e) => writeln!(output, "Could not save to {}: {}", path.display(), e)?,
+                    },
+                    None => writeln!(output, "Where to? Use `:save FILE`")?,
+                }
+            }
+            ":giveup" => {
+                writeln!(
+                    output,
+                    "You missed: {}",
+                    session.remaining_words().join(" ")
+                )?;
+                print_score(&mut output, &session)?;
+                return Ok(());
+            }
+            ":quit" => break,
+            ":help" => writeln!(output, "{}", HELP)?,
+            command if command.starts_with(':') => {
+                writeln!(output, "Unknown command {}, try :help", command)?
+            }
+            _ => match session.guess(line) {
+                Guess::Accepted { points } => {
+                    if session.puzzle().is_full_word(&line.to_lowercase()) {
+                        writeln!(output, "You found the nine letter word! +{}", points)?;
+                    } else {
+                        writeln!(output, "Yes! +{}", points)?;
+                    }
+                    if session.remaining_words().is_empty() {
+                        writeln!(output, "That's every word!")?;
+                        print_score(&mut output, &session)?;
+                        return Ok(());
+                    }
+                }
+                Guess::AlreadyFound => writeln!(output, "Already found")?,
+                Guess::Invalid(reason) => writeln!(output, "No, that {}", reason)?,
+                Guess::Paused => writeln!(output, "{}", PAUSED)?,
+                Guess::TimeUp => return time_up(&mut output, &session),
+            },
+        }
+    }
+
+    writeln!(output, "{}", session.summary(BEST_MISSED))?;
+    if let Some(path) = save_path {
+        session.pause();
+        session.save(&path)?;
+        writeln!(output, "Saved to {}", path.display())?;
+    }
+    Ok(())
+}
+
+fn time_up<W: Write>(output: &mut W, session: &GameSession) -> io::Result<()> {
+    writeln!(output, "Time's up!")?;
+    writeln!(output, "{}", session.summary(BEST_MISSED))
+}
+
+fn print_board<W: Write>(output: &mut W, board: &NineP, highlight: bool) -> io::Result<()> {
+    writeln!(output, "{}", board.render(highlight))?;
+    if let Some(centre) = board.centre() {
+        writeln!(
+            output,
+            "Every word must use {}",
+            centre.to_ascii_uppercase()
+        )?;
+    }
+    Ok(())
+}
+
+fn format_time(time: Duration) -> String {
+    // Round up, so there is never "0:00" left while the game is still going
+    let seconds = time.as_millis().div_ceil(1000);
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+fn print_score<W: Write>(output: &mut W, session: &GameSession) -> io::Result<()> {
+    writeln!(
+        output,
+        "{} of {} words, {} of {} points ({:.0}%), rated {}",
+        session.found_words().len(),
+        session.total_words(),
+        session.score(),
+        session.max_score(),
+        session.progress(),
+        session.rating()
+    )?;
+    if session.found_full_word() {
+        writeln!(output, "You found the nine letter word")?;
+    }
+    if let Some(timer) = session.timer() {
+        writeln!(output, "{} left", format_time(timer.remaining()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use nine_q_lib::word_tree::WordTree;
+
+    use super::*;
+
+    fn session() -> GameSession {
+        let words = ["aspersion", "person", "prison", "rosin", "spire"].map(String::from);
+        GameSession::new(NineP::new("aspersion".to_string(), WordTree::new(&words)))
+    }
+
+    /// Plays `input` a line at a time, returning everything printed.
+    fn run(input: &str) -> String {
+        run_session(session(), input)
+    }
+
+    fn run_session(session: GameSession, input: &str) -> String {
+        let mut output = Vec::new();
+        play(session, None, input.as_bytes(), &mut output, false).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn guesses() {
+        let output = run("prison\nprison\nzzzz\naspersion\n:quit\n:found\n");
+        assert!(output.contains("Yes! +6"));
+        assert!(output.contains("Already found"));
+        assert!(output.contains("No, that"));
+        assert!(output.contains("You found the nine letter word!"));
+        // Nothing after :quit is read
+        assert!(!output.contains("aspersion prison"));
+        assert_eq!(output.matches("> ").count(), 5);
+    }
+
+    #[test]
+    fn every_word_ends_the_game() {
+        let output = run("aspersion\nperson\nprison\nrosin\nspire\n:help\n");
+        assert!(output.contains("That's every word!"));
+        assert_eq!(output.matches("Type a word").count(), 1);
+    }
+
+    #[test]
+    fn shuffle() {
+        let output = run(":shuffle\n");
+        // The board is printed once at the start and again after shuffling
+        assert_eq!(output.matches("+---+---+---+").count(), 8);
+    }
+
+    #[test]
+    fn pause_hides_hints() {
+        let session = session().with_time_limit(Duration::from_secs(3600));
+        let output = run_session(
+            session,
+            "rosin\n:pause\n:hint\n:reveal\n:found\n:resume\n:hint\n",
+        );
+        assert_eq!(output.matches(PAUSED).count(), 3);
+        assert!(!output.contains("starts with"));
+        // Hints come back once the clock is running again
+        assert_eq!(output.matches("words left").count(), 1);
+    }
+
+    #[test]
+    fn time_up_ends_the_game() {
+        let session = session().with_time_limit(Duration::ZERO);
+        let output = run_session(session, ":reveal\n:hint\n");
+        assert!(output.contains("Time's up!"));
+        assert!(!output.contains("starts with"));
+        assert_eq!(output.matches("> ").count(), 1);
+    }
+
+    #[test]
+    fn save() {
+        let path = std::env::temp_dir().join(format!("nine_q_play_{}.txt", std::process::id()));
+        let output = run(&format!("rosin\n:save {}\n", path.display()));
+        assert!(output.contains(&format!("Saved to {}", path.display())));
+        let saved = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert!(saved.contains("found rosin"));
+    }
+
+    #[test]
+    fn failed_save_keeps_playing() {
+        let path = std::env::temp_dir()
+            .join("nine_q_missing_dir")
+            .join("save.txt");
+        let output = run(&format!(":save {}\nprison\n:quit\n", path.display()));
+        assert!(output.contains("Could not save to"));
+        assert!(output.contains("Yes! +6"));
+    }
+}
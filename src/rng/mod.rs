@@ -0,0 +1,122 @@
+use std::io;
+
+/// Small, seedable pseudo random number generator (SplitMix64).
+///
+/// Puzzles have to come out the same for a given seed on every platform and every release, so
+/// this is kept in-tree rather than relying on an external crate's algorithm staying put.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..bound`. `bound` must not be zero.
+    pub fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    /// Fisher-Yates shuffle.
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i + 1);
+            items.swap(i, j);
+        }
+    }
+}
+
+/// 64-bit FNV-1a hash, for turning text into a seed that is stable across platforms and releases.
+pub fn hash(bytes: &[u8]) -> u64 {
+    let mut hasher = Fnv::new();
+    hasher.add(bytes);
+    hasher.finish()
+}
+
+/// [`hash`] a piece at a time, which can also be written to like a file.
+#[derive(Debug, Clone)]
+pub struct Fnv {
+    hash: u64,
+}
+
+impl Default for Fnv {
+    fn default() -> Self {
+        Fnv {
+            hash: 0xcbf2_9ce4_8422_2325,
+        }
+    }
+}
+
+impl Fnv {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.hash ^= *byte as u64;
+            self.hash = self.hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    pub fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+impl io::Write for Fnv {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        self.add(bytes);
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{hash, Fnv, Rng};
+
+    #[test]
+    fn fnv1a() {
+        assert_eq!(hash(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(hash(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(hash(b"foobar"), 0x8594_4171_f739_67e8);
+
+        let mut hasher = Fnv::new();
+        hasher.add(b"foo");
+        hasher.add(b"bar");
+        assert_eq!(hasher.finish(), hash(b"foobar"));
+    }
+
+    #[test]
+    fn deterministic() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+        assert_ne!(Rng::new(1).next_u64(), Rng::new(2).next_u64());
+    }
+
+    #[test]
+    fn shuffle_keeps_items() {
+        let mut items: Vec<u32> = (0..20).collect();
+        Rng::new(7).shuffle(&mut items);
+        assert_ne!(items, (0..20).collect::<Vec<u32>>());
+        items.sort();
+        assert_eq!(items, (0..20).collect::<Vec<u32>>());
+    }
+}
@@ -1,29 +1,47 @@
-use std::{cell::RefCell, default::Default};
+use std::default::Default;
+use std::io::{self, Read, Write};
 
-use log::{debug, error, info, trace, warn};
-use pbr::ProgressBar;
+use log::{error, info, trace, warn};
 use rayon::prelude::*;
 
+use crate::rng;
+
 #[derive(Default, Clone)]
 pub struct WordTree {
     root: LetterNode,
 }
 
-#[derive(Clone, Debug)]
+/// Whether a letter can be used more than once in a word when solving.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LetterUse {
+    /// Each available letter can be used once, so a word can only repeat a letter if it is
+    /// available more than once.
+    #[default]
+    Once,
+    /// Available letters can be used any number of times, as in Spelling Bee.
+    Reuse,
+}
+
+#[derive(Clone, Debug, Default)]
 pub struct LetterNode {
-    children: Vec<Option<LetterNode>>,
+    /// Bit `i` is set if there is a child for the `i`th letter of the alphabet.
+    letters: u32,
+    /// Only the children that exist, in alphabetical order, so leaves take no extra memory.
+    children: Vec<LetterNode>,
     is_terminator: bool,
 }
 
-impl Default for LetterNode {
-    fn default() -> Self {
-        LetterNode {
-            // 26 Nones
-            children: vec![None; 26],
-            is_terminator: false,
-        }
-    }
-}
+/// Start of every tree written by [`WordTree::write`], bumped whenever the format changes.
+const MAGIC: &[u8] = b"nine_q tree 1\n";
+
+/// Bit set in a node's mask when it ends a word, after the 26 bits for its children.
+const TERMINATOR_BIT: u32 = 1 << 26;
+
+/// The bits of a node's mask for its children.
+const LETTER_BITS: u32 = TERMINATOR_BIT - 1;
+
+/// Deepest node [`WordTree::read`] will follow, so a corrupt file cannot overflow the stack.
+const MAX_DEPTH: usize = 256;
 
 const LETTER_FROM_ID: &[char] = &[
     'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r', 's',
@@ -74,12 +92,8 @@ impl WordTree {
         tree.generate(&mut words);
         trace!(
             "First layer of tree {:?}",
-            tree.root
-                .children
-                .clone()
-                .into_iter()
-                .zip(0..tree.root.children.len())
-                .map(|(c, i)| match c.is_some() {
+            (0..26)
+                .map(|i| match tree.root.letters & (1 << i) != 0 {
                     true => LETTER_FROM_ID[i],
                     false => '_',
                 })
@@ -102,12 +116,77 @@ impl WordTree {
         info!("Found {} total words in tree", result.len());
         result
     }
-    pub fn solve(&self, available_letters: &String) -> Vec<String> {
+
+    /// The node for the empty prefix, for walking the tree a letter at a time.
+    pub fn root(&self) -> &LetterNode {
+        &self.root
+    }
+
+    /// Hash of every word in the tree, to tell whether two word lists are the same.
+    ///
+    /// Hashes the shape of the tree as [`Self::write`] would save it, which is the same for the
+    /// same words however they were added, without building each word.
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = rng::Fnv::new();
+        self.root.write(&mut hasher).expect("hashing cannot fail");
+        hasher.finish()
+    }
+
+    /// Writes the tree in a compact binary form that [`Self::read`] turns back into a tree much
+    /// faster than building it from the words again.
+    ///
+    /// After a header, each node is a little endian `u32` with a bit for each child letter and
+    /// one for whether it ends a word, followed by its children in alphabetical order.
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(MAGIC)?;
+        self.root.write(&mut writer)?;
+        writer.flush()
+    }
+
+    pub fn read<R: Read>(mut reader: R) -> io::Result<Self> {
+        let mut magic = [0; MAGIC.len()];
+        reader.read_exact(&mut magic)?;
+        if magic != MAGIC {
+            return Err(invalid("not a nine_q word tree"));
+        }
+        Ok(WordTree {
+            root: LetterNode::read(&mut reader, 0)?,
+        })
+    }
+
+    /// Takes `word` out of the tree, returning whether it was there.
+    ///
+    /// Branches left without any words are dropped, so the tree is the same as one built without
+    /// the word.
+    pub fn remove(&mut self, word: &str) -> bool {
+        let letters = word.chars().collect::<Vec<char>>();
+        self.root.remove(&letters)
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        let mut node = &self.root;
+        for c in word.chars() {
+            match node.child(c) {
+                Some(child) => node = child,
+                None => return false,
+            }
+        }
+        node.is_terminator()
+    }
+
+    pub fn solve(&self, available_letters: &str) -> Vec<String> {
+        self.solve_with(available_letters, LetterUse::Once)
+    }
+
+    pub fn solve_with(&self, available_letters: &str, letter_use: LetterUse) -> Vec<String> {
         let mut available_letters: Vec<char> = available_letters.chars().collect();
         available_letters.sort();
+        if letter_use == LetterUse::Reuse {
+            available_letters.dedup();
+        }
         let mut results = Vec::new();
         self.root
-            .solve(available_letters, String::new(), &mut results);
+            .solve(available_letters, letter_use, String::new(), &mut results);
         results
     }
 }
@@ -120,8 +199,13 @@ impl LetterNode {
             .unwrap_or(&"".to_string())
             .is_empty();
 
-        for i in 1..remaining_words.len() - 1 {
-            if remaining_words[i].is_empty() {
+        for (i, word) in remaining_words
+            .iter()
+            .enumerate()
+            .take(remaining_words.len().saturating_sub(1))
+            .skip(1)
+        {
+            if word.is_empty() {
                 warn!("Empty string was found at index {}", i);
             }
         }
@@ -176,68 +260,153 @@ impl LetterNode {
             }
         }
 
+        let mut letters = 0;
+        for (i, child) in children.iter().enumerate() {
+            if child.is_some() {
+                letters |= 1 << i;
+            }
+        }
         LetterNode {
-            children: children.into(),
+            letters,
+            children: children.into_iter().flatten().collect(),
             is_terminator,
         }
     }
 
-    fn is_terminator(&self) -> bool {
+    /// Whether the letters leading to this node spell a whole word.
+    pub fn is_terminator(&self) -> bool {
         self.is_terminator
     }
 
+    /// The node reached by adding `letter`, if any word continues that way.
+    pub fn child(&self, letter: char) -> Option<&LetterNode> {
+        if !letter.is_ascii_lowercase() {
+            return None;
+        }
+        self.child_by_id(letter_to_id(&letter))
+    }
+
+    fn child_by_id(&self, id: usize) -> Option<&LetterNode> {
+        match self.letters & (1 << id) != 0 {
+            // Children are packed, so count the ones before this letter to find it
+            true => Some(&self.children[(self.letters & ((1 << id) - 1)).count_ones() as usize]),
+            false => None,
+        }
+    }
+
+    /// Takes the word spelled by `letters` out from below this node, returning whether it was
+    /// there.
+    fn remove(&mut self, letters: &[char]) -> bool {
+        let Some((&letter, rest)) = letters.split_first() else {
+            return std::mem::take(&mut self.is_terminator);
+        };
+        if !letter.is_ascii_lowercase() {
+            return false;
+        }
+        let id = letter_to_id(&letter);
+        if self.letters & (1 << id) == 0 {
+            return false;
+        }
+        let index = (self.letters & ((1 << id) - 1)).count_ones() as usize;
+        let removed = self.children[index].remove(rest);
+        let child = &self.children[index];
+        if !child.is_terminator && child.children.is_empty() {
+            self.children.remove(index);
+            self.letters &= !(1 << id);
+        }
+        removed
+    }
+
+    /// Each child with the id of its letter, in alphabetical order.
+    fn children(&self) -> impl Iterator<Item = (usize, &LetterNode)> {
+        (0..26)
+            .filter(|i| self.letters & (1 << i) != 0)
+            .zip(self.children.iter())
+    }
+
+    fn write<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let mask = match self.is_terminator {
+            true => self.letters | TERMINATOR_BIT,
+            false => self.letters,
+        };
+        writer.write_all(&mask.to_le_bytes())?;
+        for child in &self.children {
+            child.write(writer)?;
+        }
+        Ok(())
+    }
+
+    fn read<R: Read>(reader: &mut R, depth: usize) -> io::Result<Self> {
+        if depth > MAX_DEPTH {
+            return Err(invalid("tree is too deep"));
+        }
+        let mut bytes = [0; 4];
+        reader.read_exact(&mut bytes)?;
+        let mask = u32::from_le_bytes(bytes);
+        if mask & !(TERMINATOR_BIT | LETTER_BITS) != 0 {
+            return Err(invalid("unknown bits in node"));
+        }
+
+        let letters = mask & LETTER_BITS;
+        let mut children = Vec::with_capacity(letters.count_ones() as usize);
+        for _ in 0..letters.count_ones() {
+            children.push(LetterNode::read(reader, depth + 1)?);
+        }
+        Ok(LetterNode {
+            letters,
+            children,
+            is_terminator: mask & TERMINATOR_BIT != 0,
+        })
+    }
+
     pub fn get_words(&self, working_word: String, results: &mut Vec<String>) {
         trace!(
             "Current word is {}, viable children = {}",
             working_word,
-            self.children
-                .clone()
-                .into_iter()
-                .filter(|c| c.is_some())
-                .count()
-                + if self.is_terminator { 1 } else { 0 }
+            self.children.len() + if self.is_terminator { 1 } else { 0 }
         );
-        for (i, child) in (0_usize..self.children.len()).zip(self.children.iter()) {
-            if let Some(child) = child {
-                let mut next_word = working_word.clone();
-                next_word.push(LETTER_FROM_ID[i]);
-                trace!(
-                    "Decending to child {} with word {}",
-                    LETTER_FROM_ID[i],
-                    next_word
-                );
-                (*child).get_words(next_word, results);
-            }
+        for (i, child) in self.children() {
+            let mut next_word = working_word.clone();
+            next_word.push(LETTER_FROM_ID[i]);
+            trace!(
+                "Decending to child {} with word {}",
+                LETTER_FROM_ID[i],
+                next_word
+            );
+            child.get_words(next_word, results);
         }
 
         if self.is_terminator() {
-            info!("Adding word {} to results", working_word);
+            trace!("Adding word {} to results", working_word);
             results.push(working_word);
         }
     }
 
-    fn solve(&self, available_letters: Vec<char>, current_word: String, results: &mut Vec<String>) {
+    fn solve(
+        &self,
+        available_letters: Vec<char>,
+        letter_use: LetterUse,
+        current_word: String,
+        results: &mut Vec<String>,
+    ) {
         let mut thin_letters = available_letters.clone();
         thin_letters.dedup();
         for c in thin_letters.iter() {
-            if self.children[letter_to_id(c)].is_some() {
+            if let Some(child) = self.child_by_id(letter_to_id(c)) {
                 // There is at least one word that has this caracter in this location
                 // This character must exist in the pool of available_letters
-                let first_index = available_letters.binary_search(c).unwrap();
                 let mut next_available_letters = available_letters.clone();
-                next_available_letters.remove(first_index);
+                if letter_use == LetterUse::Once {
+                    let first_index = available_letters.binary_search(c).unwrap();
+                    next_available_letters.remove(first_index);
+                }
 
                 // Set up next word
                 let mut next_word = current_word.clone();
                 next_word.push(*c);
 
                 // Recurse
-                self.children
-                    .get(letter_to_id(c))
-                    .unwrap()
-                    .as_ref()
-                    .unwrap()
-                    .solve(next_available_letters, next_word, results);
+                child.solve(next_available_letters, letter_use, next_word, results);
             }
         }
         if self.is_terminator() {
@@ -246,11 +415,18 @@ impl LetterNode {
     }
 }
 
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("Invalid word tree: {}", message),
+    )
+}
+
 #[cfg(test)]
 mod test {
     use crate::init_logger;
 
-    use super::WordTree;
+    use super::{LetterUse, WordTree};
 
     #[test]
     fn zoo() {
@@ -282,4 +458,86 @@ mod test {
 
         assert_eq!(test_data.get_words(), words);
     }
+
+    #[test]
+    fn contains_words() {
+        init_logger();
+        let test_data = WordTree::new(&["kind".to_owned(), "kinder".to_owned()]);
+
+        assert!(test_data.contains("kind"));
+        assert!(test_data.contains("kinder"));
+        assert!(!test_data.contains("kin"));
+        assert!(!test_data.contains("kindest"));
+        assert!(!test_data.contains("Kind"));
+    }
+
+    #[test]
+    fn reuse_letters() {
+        init_logger();
+        let test_data = WordTree::new(&[
+            "kin".to_owned(),
+            "kind".to_owned(),
+            "kinkind".to_owned(),
+            "dink".to_owned(),
+        ]);
+
+        let mut once = test_data.solve("dkin");
+        once.sort();
+        assert_eq!(once, ["dink", "kin", "kind"]);
+
+        let mut reuse = test_data.solve_with("dkin", LetterUse::Reuse);
+        reuse.sort();
+        assert_eq!(reuse, ["dink", "kin", "kind", "kinkind"]);
+    }
+
+    #[test]
+    fn fingerprints() {
+        init_logger();
+        let a = WordTree::new(&["kind".to_owned(), "kinder".to_owned()]);
+        let b = WordTree::new(&["kinder".to_owned(), "kind".to_owned(), "kind".to_owned()]);
+        let c = WordTree::new(&["kind".to_owned()]);
+
+        assert_eq!(a.fingerprint(), b.fingerprint());
+        assert_ne!(a.fingerprint(), c.fingerprint());
+    }
+
+    #[test]
+    fn remove_words() {
+        init_logger();
+        let mut tree = WordTree::new(&["kin", "kind", "kinder", "zoo"].map(String::from));
+        assert!(tree.remove("kinder"));
+        assert!(tree.remove("zoo"));
+        assert!(!tree.remove("zoo"));
+        assert!(!tree.remove("ki"));
+        assert!(!tree.remove("Kin"));
+        assert!(tree.contains("kin") && tree.contains("kind"));
+
+        // Empty branches are dropped along with the words
+        let built = WordTree::new(&["kin", "kind"].map(String::from));
+        assert_eq!(tree.fingerprint(), built.fingerprint());
+    }
+
+    #[test]
+    fn binary_round_trip() {
+        init_logger();
+        let tree = WordTree::new(&["kin".to_owned(), "kind".to_owned(), "zoo".to_owned()]);
+        let mut bytes = Vec::new();
+        tree.write(&mut bytes).unwrap();
+        // Header, then one mask for each of the 8 nodes
+        assert_eq!(bytes.len(), 14 + 8 * 4);
+
+        let read = WordTree::read(bytes.as_slice()).unwrap();
+        assert_eq!(read.fingerprint(), tree.fingerprint());
+        assert!(read.contains("kind"));
+        assert!(!read.contains("ki"));
+
+        let error = WordTree::read(&bytes[..bytes.len() - 1])
+            .map(|_| ())
+            .unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::UnexpectedEof);
+        let error = WordTree::read(&b"nine_q save 1\n"[..])
+            .map(|_| ())
+            .unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    }
 }